@@ -1,4 +1,4 @@
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use dbus_message_parser::message::Message;
 use std::time::Duration;
@@ -69,6 +69,20 @@ fn decode(c: &mut Criterion) {
     group.finish();
 }
 
+fn decode_ref(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_ref");
+    for (msg, name) in MESSAGES.iter() {
+        let name = name.to_owned();
+        let throughput = Throughput::Bytes(msg.len() as u64);
+        let benchmark_id = BenchmarkId::new(name, msg.len());
+        group.throughput(throughput);
+        group.bench_with_input(benchmark_id, msg, |b, msg| {
+            b.iter(|| Message::decode_ref(msg).unwrap())
+        });
+    }
+    group.finish();
+}
+
 fn encode(c: &mut Criterion) {
     let mut group = c.benchmark_group("encode");
     for (msg, name) in MESSAGES.iter() {
@@ -86,11 +100,32 @@ fn encode(c: &mut Criterion) {
     group.finish();
 }
 
+fn encode_into(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_into");
+    for (msg, name) in MESSAGES.iter() {
+        let msg = Bytes::from_static(msg);
+        let (msg, _) = Message::decode(msg).unwrap();
+        let bytes = msg.encode().unwrap();
+        let name = name.to_owned();
+        let throughput = Throughput::Bytes(bytes.len() as u64);
+        let benchmark_id = BenchmarkId::new(name, bytes.len());
+        group.throughput(throughput);
+        let mut buf = BytesMut::with_capacity(bytes.len());
+        group.bench_with_input(benchmark_id, &msg, |b, msg| {
+            b.iter(|| {
+                buf.clear();
+                msg.encode_into(&mut buf).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default()
         .sample_size(1024)
         .measurement_time(Duration::from_secs(10));
-    targets = decode, encode
+    targets = decode, decode_ref, encode, encode_into
 }
 criterion_main!(benches);
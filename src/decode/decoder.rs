@@ -1,28 +1,22 @@
-use crate::decode::{DecodeError, DecodeResult};
-use crate::message::MAXIMUM_MESSAGE_LENGTH;
-use bytes::Bytes;
-#[cfg(not(target_family = "unix"))]
-use std::marker::PhantomData;
+use crate::decode::{
+    DecodeError, DecodeOptions, DecodePathElement, DecodeResult, LocatedDecodeError,
+};
 #[cfg(target_family = "unix")]
-use std::os::unix::io::RawFd;
-
-fn check_length(buf: Bytes) -> Bytes {
-    if buf.len() < MAXIMUM_MESSAGE_LENGTH {
-        buf
-    } else {
-        buf.slice(..MAXIMUM_MESSAGE_LENGTH)
-    }
-}
+use crate::value::UnixFd;
 
 pub struct Decoder<'a> {
-    pub(crate) buf: Bytes,
+    pub(crate) buf: &'a [u8],
     pub(crate) offset: usize,
+    /// The FDs, which are referenced by the body. If there are no FDs, then only the indices of the
+    /// FDs are decoded.
     #[cfg(target_family = "unix")]
-    pub(crate) fds: &'a [RawFd],
-    #[cfg(target_family = "unix")]
-    pub(crate) offset_fds: Option<usize>,
-    #[cfg(not(target_family = "unix"))]
-    phantom: PhantomData<&'a usize>,
+    pub(crate) fds: Option<&'a [UnixFd]>,
+    /// The number of FDs, which is declared in the `UNIX_FDS` header field of the message, which
+    /// is currently decoded. The indices of the FDs in the body have to be smaller.
+    pub(crate) unix_fds: Option<u32>,
+    pub(crate) options: DecodeOptions,
+    /// The path to the value, which is currently decoded.
+    pub(crate) path: Vec<DecodePathElement>,
 }
 
 impl<'a> Decoder<'a> {
@@ -33,39 +27,56 @@ impl<'a> Decoder<'a> {
             let padding_length = a - remain;
             let padding = self.read(padding_length)?;
             for b in padding {
-                if b != 0 {
-                    return Err(DecodeError::Padding(b));
+                if *b != 0 {
+                    return Err(DecodeError::Padding(*b));
                 }
             }
         }
         Ok(())
     }
 
-    pub fn new(buf: Bytes) -> Decoder<'static> {
-        let buf = check_length(buf);
+    pub fn new(buf: &'a [u8]) -> Decoder<'a> {
+        Decoder::with_options(buf, DecodeOptions::default())
+    }
+
+    pub fn with_options(buf: &'a [u8], options: DecodeOptions) -> Decoder<'a> {
         Decoder {
             buf,
             offset: 0,
             #[cfg(target_family = "unix")]
-            fds: &[],
-            #[cfg(target_family = "unix")]
-            offset_fds: None,
-            #[cfg(not(target_family = "unix"))]
-            phantom: PhantomData,
+            fds: None,
+            unix_fds: None,
+            options,
+            path: Vec::new(),
         }
     }
 
     #[cfg(target_family = "unix")]
-    pub fn new_with_fds(buf: Bytes, fds: &[RawFd]) -> Decoder<'_> {
-        let buf = check_length(buf);
+    pub fn new_with_fds(buf: &'a [u8], fds: &'a [UnixFd]) -> Decoder<'a> {
+        Decoder::with_fds_and_options(buf, fds, DecodeOptions::default())
+    }
+
+    #[cfg(target_family = "unix")]
+    pub fn with_fds_and_options(
+        buf: &'a [u8],
+        fds: &'a [UnixFd],
+        options: DecodeOptions,
+    ) -> Decoder<'a> {
         Decoder {
             buf,
             offset: 0,
-            fds,
-            offset_fds: None,
+            fds: Some(fds),
+            unix_fds: None,
+            options,
+            path: Vec::new(),
         }
     }
 
+    /// Add the current offset and path to a [`DecodeError`].
+    pub(crate) fn located(&self, error: DecodeError) -> LocatedDecodeError {
+        LocatedDecodeError::new(error, self.offset, self.path.clone())
+    }
+
     #[inline]
     pub(crate) fn checked_add(left: usize, right: usize) -> DecodeResult<usize> {
         if let Some(result) = left.checked_add(right) {
@@ -75,29 +86,28 @@ impl<'a> Decoder<'a> {
         }
     }
 
+    /// Enter a container and check the total depth of the nested containers.
     #[inline]
-    pub(super) fn read(&mut self, length: usize) -> DecodeResult<Bytes> {
-        let start = self.offset;
-        self.offset = Decoder::checked_add(start, length)?;
-        let buf_len = self.buf.len();
-        if self.offset <= buf_len {
-            Ok(self.buf.slice(start..self.offset))
+    pub(crate) fn container_depth(&self, container_depth: u8) -> DecodeResult<u8> {
+        let container_depth = container_depth.saturating_add(1);
+        if self.options.maximum_container_depth < container_depth {
+            Err(DecodeError::ContainerDepth(container_depth))
         } else {
-            Err(DecodeError::NotEnoughBytes(buf_len, self.offset))
+            Ok(container_depth)
         }
     }
-}
 
-#[test]
-fn check_maximum_length() {
-    use bytes::BytesMut;
-    let b: [u8; 131072] = [0; 131072]; // 16 KiB
-    let mut bytes = BytesMut::new();
-    while bytes.len() < MAXIMUM_MESSAGE_LENGTH {
-        bytes.extend_from_slice(&b[..]);
+    #[inline]
+    pub(super) fn read(&mut self, length: usize) -> DecodeResult<&'a [u8]> {
+        let start = self.offset;
+        let end = Decoder::checked_add(start, length)?;
+        let buf_len = self.buf.len();
+        if end <= buf_len {
+            self.offset = end;
+            Ok(&self.buf[start..end])
+        } else {
+            // The offset is not changed, so the error is located at the start of the bytes.
+            Err(DecodeError::NotEnoughBytes(buf_len, end))
+        }
     }
-    bytes.extend_from_slice(&b[..]);
-    let bytes = bytes.freeze();
-    let bytes = check_length(bytes);
-    assert_eq!(bytes.len(), MAXIMUM_MESSAGE_LENGTH);
 }
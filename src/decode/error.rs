@@ -1,16 +1,19 @@
 use crate::{
-    decode::MAXIMUM_VARIANT_DEPTH,
     message::{MessageHeaderError, MessageHeaderFieldsError},
     value::{
         BusError, ErrorError, InterfaceError, MemberError, ObjectPathError, StructError, Type,
-        TypeError, MAXIMUM_ARRAY_LENGTH,
+        TypeError,
     },
 };
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::ErrorKind;
 use std::str::Utf8Error;
 use thiserror::Error;
 
 pub type DecodeResult<T> = Result<T, DecodeError>;
 
+pub type LocatedDecodeResult<T> = Result<T, LocatedDecodeError>;
+
 /// An enum representing all errors, which can occur during the decoding.
 #[derive(Debug, PartialEq, Error)]
 pub enum DecodeError {
@@ -22,6 +25,8 @@ pub enum DecodeError {
     Utf8Error(#[from] Utf8Error),
     #[error("Last byte is not null: {0}")]
     StringNotNull(u8),
+    #[error("String contains a null byte at position {0}")]
+    StringNul(usize),
     #[error("Could not decode Bus: {0}")]
     BusError(#[from] BusError),
     #[error("Could not decode ObjectPath: {0}")]
@@ -38,7 +43,7 @@ pub enum DecodeError {
     StructError(#[from] StructError),
     #[error("Padding is not zero: {0}")]
     Padding(u8),
-    #[error("Array length is too big: {MAXIMUM_ARRAY_LENGTH} < {0}")]
+    #[error("Array length is too big: {0}")]
     ArrayTooBig(u32),
     #[error("Array is invalid: got {0} excepted {1}")]
     ArrayInvalidLength(usize, usize),
@@ -46,14 +51,18 @@ pub enum DecodeError {
     Endianness(u8),
     #[error("Could not decode MessageType: {0}")]
     MessageType(u8),
-    #[error("Could not decode MessageFlags: {0}")]
-    MessageFlags(u8),
+    #[error("Major protocol version is not supported: {0}")]
+    UnsupportedVersion(u8),
     #[error("The body length is zero, but there is a body signature '{0:?}'")]
     BodyLengthZero(Vec<Type>),
     #[error("The body signature is missing, but there body length 0 != {0}")]
     BodySignatureMissing(u32),
     #[error("Not enough FDs: got {0} offset {1}")]
     NotEnoughFds(usize, usize),
+    #[error("Index of a FD is not smaller than the UNIX_FDS header field: {0} >= {1}")]
+    UnixFdIndex(u32, u32),
+    #[error("Could not duplicate the FD: {0}")]
+    DuplicateFd(ErrorKind),
     #[error("Could not the body: expected {0} got {1}")]
     BodyLength(usize, usize),
     #[error("Could not decode MessageHeader: {0}")]
@@ -62,6 +71,145 @@ pub enum DecodeError {
     MessageHeaderFieldsError(#[from] MessageHeaderFieldsError),
     #[error("Integer overflows occours: {0} + {1}")]
     IntegerOverflow(usize, usize),
-    #[error("Variant depth is too big: {MAXIMUM_VARIANT_DEPTH} < {0}")]
+    #[error("Variant depth is too big: {0}")]
     VariantDepth(u8),
+    #[error("Container depth is too big: {0}")]
+    ContainerDepth(u8),
+    #[error("Message is too long: {0}")]
+    MessageTooLong(usize),
+    #[error("Unknown MessageType, the message has to be ignored: {0}")]
+    UnknownMessageType(u8),
+    #[error("Framing offset is invalid: {0}")]
+    FramingOffset(usize),
+    #[error("Size of a fixed size type is invalid: expected {0} got {1}")]
+    FixedSize(usize, usize),
+    #[error("Variant does not contain a separator between the value and the type")]
+    VariantSeparator,
+    #[error("Serial does not fit into 32 bits: {0}")]
+    SerialOverflow(u64),
+    #[error("Number of a header field does not fit into 8 bits: {0}")]
+    HeaderFieldNumber(u64),
+    #[error("Body is not a struct: {0}")]
+    BodyType(Type),
+    #[error("Maybe type is not supported by the D-Bus format")]
+    MaybeType,
+}
+
+/// An element of the path to the value, which could not be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodePathElement {
+    /// The header of the message.
+    Header,
+    /// The argument of the body at the given index.
+    BodyArgument(usize),
+    /// The element of an array at the given index.
+    ArrayElement(usize),
+    /// The field of a struct at the given index.
+    StructField(usize),
+    /// The key of a dict entry.
+    DictKey,
+    /// The value of a dict entry.
+    DictValue,
+    /// The value of a variant.
+    Variant,
+}
+
+impl Display for DecodePathElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DecodePathElement::Header => write!(f, "header"),
+            DecodePathElement::BodyArgument(i) => write!(f, "body arg {}", i),
+            DecodePathElement::ArrayElement(i) => write!(f, "array element {}", i),
+            DecodePathElement::StructField(i) => write!(f, "struct field {}", i),
+            DecodePathElement::DictKey => write!(f, "dict key"),
+            DecodePathElement::DictValue => write!(f, "dict value"),
+            DecodePathElement::Variant => write!(f, "variant"),
+        }
+    }
+}
+
+/// A [`DecodeError`] with the location, where it occurred.
+///
+/// The location consists of the offset of the decoder, when the error occurred, and the path to
+/// the value, which could not be decoded.
+///
+/// # Example
+/// ```
+/// # use bytes::Bytes;
+/// # use dbus_message_parser::decode::{DecodeError, DecodePathElement};
+/// # use dbus_message_parser::message::Message;
+/// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\xff";
+/// let bytes = Bytes::copy_from_slice(&msg[..]);
+///
+/// let error = Message::decode(bytes).unwrap_err();
+/// assert_eq!(error.get_error(), &DecodeError::StringNotNull(0xff));
+/// assert_eq!(error.get_offset(), msg.len());
+/// assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(0)]);
+/// assert_eq!(
+///     error.to_string(),
+///     "Last byte is not null: 255 at offset 90 (body arg 0)"
+/// );
+/// ```
+#[derive(Debug, PartialEq, Error)]
+#[error("{error} at offset {offset} ({})", display_path(.path))]
+pub struct LocatedDecodeError {
+    #[source]
+    error: DecodeError,
+    offset: usize,
+    path: Vec<DecodePathElement>,
+}
+
+fn display_path(path: &[DecodePathElement]) -> String {
+    let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+    path.join(" \u{2192} ")
+}
+
+impl LocatedDecodeError {
+    pub(crate) fn new(
+        error: DecodeError,
+        offset: usize,
+        path: Vec<DecodePathElement>,
+    ) -> LocatedDecodeError {
+        LocatedDecodeError {
+            error,
+            offset,
+            path,
+        }
+    }
+
+    /// Get the [`DecodeError`].
+    #[inline]
+    pub const fn get_error(&self) -> &DecodeError {
+        &self.error
+    }
+
+    /// Get the offset of the decoder, when the error occurred.
+    #[inline]
+    pub const fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Get the path to the value, which could not be decoded.
+    #[inline]
+    pub fn get_path(&self) -> &[DecodePathElement] {
+        &self.path
+    }
+
+    /// Convert into the [`DecodeError`] without the location.
+    #[inline]
+    pub fn into_error(self) -> DecodeError {
+        self.error
+    }
+}
+
+impl From<LocatedDecodeError> for DecodeError {
+    fn from(error: LocatedDecodeError) -> Self {
+        error.error
+    }
+}
+
+impl PartialEq<DecodeError> for LocatedDecodeError {
+    fn eq(&self, other: &DecodeError) -> bool {
+        &self.error == other
+    }
 }
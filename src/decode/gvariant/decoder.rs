@@ -0,0 +1,105 @@
+use crate::decode::{
+    DecodeError, DecodeOptions, DecodePathElement, DecodeResult, Decoder, LocatedDecodeError,
+};
+use crate::encode::algin;
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+
+/// A decoder for the [GVariant format]. In contrast to the [`Decoder`], every value is decoded
+/// from the bytes between a start and an end offset, because the size of a value is given by the
+/// framing offsets of the container and not by a length prefix.
+///
+/// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+pub(crate) struct GVariantDecoder<'a> {
+    pub(crate) buf: &'a [u8],
+    pub(crate) is_le: bool,
+    /// The FDs of the message, which are referenced by the handles. If there are no FDs, then the
+    /// handles are decoded as indices.
+    #[cfg(target_family = "unix")]
+    pub(crate) fds: Option<&'a [UnixFd]>,
+    /// The number of FDs, which is declared in the `UNIX_FDS` header field. The index of every
+    /// handle has to be smaller than this number.
+    pub(crate) unix_fds: Option<u32>,
+    pub(crate) options: DecodeOptions,
+    /// The start of the value, which is currently decoded.
+    pub(crate) offset: usize,
+    /// The path to the value, which is currently decoded.
+    pub(crate) path: Vec<DecodePathElement>,
+}
+
+impl<'a> GVariantDecoder<'a> {
+    pub(crate) fn new(buf: &'a [u8], is_le: bool, options: DecodeOptions) -> GVariantDecoder<'a> {
+        GVariantDecoder {
+            buf,
+            is_le,
+            #[cfg(target_family = "unix")]
+            fds: None,
+            unix_fds: None,
+            options,
+            offset: 0,
+            path: Vec::new(),
+        }
+    }
+
+    #[cfg(target_family = "unix")]
+    pub(crate) fn with_fds(
+        buf: &'a [u8],
+        is_le: bool,
+        fds: &'a [UnixFd],
+        options: DecodeOptions,
+    ) -> GVariantDecoder<'a> {
+        GVariantDecoder {
+            buf,
+            is_le,
+            fds: Some(fds),
+            unix_fds: None,
+            options,
+            offset: 0,
+            path: Vec::new(),
+        }
+    }
+
+    /// Add the current offset and path to a [`DecodeError`].
+    pub(crate) fn located(&self, error: DecodeError) -> LocatedDecodeError {
+        LocatedDecodeError::new(error, self.offset, self.path.clone())
+    }
+
+    /// Enter a container and check the total depth of the nested containers.
+    #[inline]
+    pub(super) fn container_depth(&self, container_depth: u8) -> DecodeResult<u8> {
+        let container_depth = container_depth.saturating_add(1);
+        if self.options.maximum_container_depth < container_depth {
+            Err(DecodeError::ContainerDepth(container_depth))
+        } else {
+            Ok(container_depth)
+        }
+    }
+
+    /// Get the bytes between `start` and `end`.
+    pub(super) fn get(&self, start: usize, end: usize) -> DecodeResult<&'a [u8]> {
+        let buf_len = self.buf.len();
+        if buf_len < end {
+            Err(DecodeError::NotEnoughBytes(buf_len, end))
+        } else if end < start {
+            Err(DecodeError::FramingOffset(end))
+        } else {
+            Ok(&self.buf[start..end])
+        }
+    }
+
+    /// Decode a framing offset, which is always encoded in little endian.
+    pub(super) fn framing_offset(&self, offset: usize, offset_size: usize) -> DecodeResult<usize> {
+        let end = Decoder::checked_add(offset, offset_size)?;
+        let mut framing_offset = 0;
+        for (i, b) in self.get(offset, end)?.iter().enumerate() {
+            framing_offset |= (*b as u64) << (i * 8);
+        }
+        Ok(framing_offset as usize)
+    }
+
+    /// Apply the alignment `a` to the `offset` relative to the start of the container.
+    #[inline]
+    pub(super) fn algin(start: usize, offset: usize, a: usize) -> usize {
+        start + algin(offset - start, a)
+    }
+}
@@ -0,0 +1,217 @@
+use crate::decode::{
+    gvariant::GVariantDecoder, DecodeError, DecodeOptions, DecodePathElement, DecodeResult,
+    Decoder, LocatedDecodeError, LocatedDecodeResult,
+};
+use crate::encode::algin;
+use crate::message::{
+    Message, MessageFlags, MessageHeader, MessageHeaderFields, MessageType,
+    GVARIANT_PROTOCOL_VERSION,
+};
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+use crate::value::{gvariant_offset_size, Struct, Type, Value};
+use bytes::Bytes;
+use std::convert::TryFrom;
+
+/// The length of the fixed part of the header: the endianness, the message type, the message
+/// flags, the major protocol version, a reserved `u32` and the serial as `u64`.
+const FIXED_HEADER_LENGTH: usize = 16;
+
+impl<'a> GVariantDecoder<'a> {
+    /// Decode the header fields, which are encoded as `a{tv}`.
+    fn message_header_fields(
+        &mut self,
+        start: usize,
+        end: usize,
+    ) -> DecodeResult<MessageHeaderFields> {
+        let type_ = Type::DictEntry(Box::new((Type::Uint64, Type::Variant)));
+        self.path.push(DecodePathElement::Header);
+        let entries = self.array(&type_, start, end, 0, 0)?;
+
+        let mut values = Vec::with_capacity(entries.len());
+        for entry in entries {
+            if let Value::DictEntry(entry) = entry {
+                if let (Value::Uint64(number), value) = *entry {
+                    // The header fields store the number of a field as a byte.
+                    let number = match u8::try_from(number) {
+                        Ok(number) => number,
+                        Err(_) => return Err(DecodeError::HeaderFieldNumber(number)),
+                    };
+                    values.push(Value::Struct(Struct(vec![Value::Byte(number), value])));
+                }
+            }
+        }
+
+        let mut fields = MessageHeaderFields::try_from(values)?;
+        self.path.pop();
+        // The signature of the body is part of the body.
+        fields.signature = None;
+        Ok(fields)
+    }
+
+    /// Decode the body, which is encoded as a variant of a struct.
+    fn message_body(&mut self, start: usize, end: usize) -> DecodeResult<Vec<Value>> {
+        let bytes = self.get(start, end)?;
+        let separator = match bytes.iter().rposition(|b| *b == 0) {
+            Some(separator) => separator,
+            None => return Err(DecodeError::VariantSeparator),
+        };
+        let types = match &bytes[separator + 1..] {
+            b"()" => Vec::new(),
+            type_ => match Type::try_from(type_)? {
+                Type::Struct(types) => types,
+                type_ => return Err(DecodeError::BodyType(type_)),
+            },
+        };
+        for type_ in &types {
+            self.options.check_depth(type_, 0, 0, 0)?;
+        }
+        let types: Vec<&Type> = types.iter().collect();
+        self.struct_values(
+            &types,
+            start,
+            start + separator,
+            0,
+            0,
+            DecodePathElement::BodyArgument,
+        )
+    }
+
+    /// Decode a message, which is encoded as `(yyyyuta{tv}v)`.
+    fn message(&mut self) -> DecodeResult<Message> {
+        let end = self.buf.len();
+        let header = self.get(0, FIXED_HEADER_LENGTH)?;
+
+        let message_type = match MessageType::try_from(header[1]) {
+            Ok(message_type) => message_type,
+            Err(0) => return Err(DecodeError::MessageType(0)),
+            Err(message_type) => return Err(DecodeError::UnknownMessageType(message_type)),
+        };
+        // SAFETY: `MessageFlags` is only a set of bits, every `u8` is a valid value.
+        let message_flags = unsafe { MessageFlags::from_bits_unchecked(header[2]) };
+        let version = header[3];
+        if version != GVARIANT_PROTOCOL_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let mut serial = [0; 8];
+        serial.copy_from_slice(&header[8..]);
+        let serial = if self.is_le {
+            u64::from_le_bytes(serial)
+        } else {
+            u64::from_be_bytes(serial)
+        };
+        let serial = match u32::try_from(serial) {
+            Ok(serial) => serial,
+            Err(_) => return Err(DecodeError::SerialOverflow(serial)),
+        };
+
+        // The framing offset at the end points to the end of the header fields.
+        let offset_size = gvariant_offset_size(end);
+        let offsets_start = end - offset_size;
+        let fields_end = self.framing_offset(offsets_start, offset_size)?;
+        if fields_end < FIXED_HEADER_LENGTH || offsets_start < fields_end {
+            return Err(DecodeError::FramingOffset(fields_end));
+        }
+        let fields = self.message_header_fields(FIXED_HEADER_LENGTH, fields_end)?;
+        self.unix_fds = Some(fields.unix_fds.unwrap_or(0));
+
+        let body_start = algin(fields_end, 8);
+        if offsets_start < body_start {
+            return Err(DecodeError::NotEnoughBytes(
+                end,
+                Decoder::checked_add(body_start, offset_size)?,
+            ));
+        }
+        let body = self.message_body(body_start, offsets_start)?;
+
+        let header = MessageHeader::new(
+            self.is_le,
+            message_type,
+            message_flags,
+            version,
+            serial,
+            fields,
+        )?;
+        Ok(Message::new(header, body))
+    }
+}
+
+/// Decode the endianness flag of a message in the GVariant format.
+fn message_is_le(bytes: &[u8]) -> LocatedDecodeResult<bool> {
+    let error = match bytes.first() {
+        Some(0x6c) => return Ok(true),
+        Some(0x42) => return Ok(false),
+        Some(b) => DecodeError::Endianness(*b),
+        None => DecodeError::NotEnoughBytes(0, FIXED_HEADER_LENGTH),
+    };
+    Err(LocatedDecodeError::new(
+        error,
+        0,
+        vec![DecodePathElement::Header],
+    ))
+}
+
+impl Message {
+    /// Decode a [`Message`] in the [GVariant format], which is used by the major protocol
+    /// version 2. The bytes have to contain exactly the message, because the size of the message
+    /// is not encoded.
+    ///
+    /// The error contains the location, where the [`Message`] could not be decoded.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub fn decode_gvariant(bytes: Bytes) -> LocatedDecodeResult<Message> {
+        Message::decode_gvariant_with_options(bytes, DecodeOptions::default())
+    }
+
+    /// Decode a [`Message`] in the [GVariant format] with the given limits.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub fn decode_gvariant_with_options(
+        bytes: Bytes,
+        options: DecodeOptions,
+    ) -> LocatedDecodeResult<Message> {
+        let is_le = message_is_le(&bytes)?;
+        let mut decoder = GVariantDecoder::new(&bytes, is_le, options);
+        decoder.message().map_err(|e| decoder.located(e))
+    }
+
+    /// Decode a [`Message`] in the [GVariant format] with the given FDs and returns the offset of
+    /// the given FDs. The handles in the body are indices into the FDs, which are duplicated.
+    ///
+    /// The FDs are checked in the same way as in [`decode_with_fds`].
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    /// [`decode_with_fds`]: Message::decode_with_fds
+    #[cfg(target_family = "unix")]
+    pub fn decode_gvariant_with_fds(
+        bytes: Bytes,
+        fds: &[UnixFd],
+    ) -> LocatedDecodeResult<(Message, usize)> {
+        Message::decode_gvariant_with_fds_and_options(bytes, fds, DecodeOptions::default())
+    }
+
+    /// Decode a [`Message`] in the [GVariant format] with the given FDs and the given limits and
+    /// returns the offset of the given FDs.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    #[cfg(target_family = "unix")]
+    pub fn decode_gvariant_with_fds_and_options(
+        bytes: Bytes,
+        fds: &[UnixFd],
+        options: DecodeOptions,
+    ) -> LocatedDecodeResult<(Message, usize)> {
+        let is_le = message_is_le(&bytes)?;
+        let mut decoder = GVariantDecoder::with_fds(&bytes, is_le, fds, options);
+        let msg = decoder.message().map_err(|e| decoder.located(e))?;
+        let unix_fds = decoder.unix_fds.unwrap_or(0) as usize;
+        if fds.len() < unix_fds {
+            let error = DecodeError::NotEnoughFds(fds.len(), unix_fds);
+            return Err(LocatedDecodeError::new(
+                error,
+                0,
+                vec![DecodePathElement::Header],
+            ));
+        }
+        Ok((msg, unix_fds))
+    }
+}
@@ -0,0 +1,5 @@
+mod decoder;
+mod message;
+mod value;
+
+pub(crate) use decoder::GVariantDecoder;
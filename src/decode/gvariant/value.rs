@@ -0,0 +1,392 @@
+use crate::decode::{
+    gvariant::GVariantDecoder, DecodeError, DecodeOptions, DecodePathElement, DecodeResult,
+    Decoder, LocatedDecodeResult,
+};
+use crate::value::{gvariant_offset_size, Array, Maybe, ObjectPath, Struct, Type, Value};
+use std::convert::TryFrom;
+use std::mem::size_of;
+use std::str::from_utf8;
+
+macro_rules! decode_number {
+    ($decoder:ident, $start:ident, $end:ident, $type:ty) => {{
+        let mut bytes = [0; size_of::<$type>()];
+        bytes.copy_from_slice($decoder.get($start, $end)?);
+        if $decoder.is_le {
+            <$type>::from_le_bytes(bytes)
+        } else {
+            <$type>::from_be_bytes(bytes)
+        }
+    }};
+}
+
+impl<'a> GVariantDecoder<'a> {
+    /// Decode a string, which has to end with a null byte and must not contain another null byte.
+    fn d_str(&self, start: usize, end: usize) -> DecodeResult<&'a str> {
+        match self.get(start, end)?.split_last() {
+            Some((0, string)) => {
+                if let Some(i) = string.iter().position(|b| *b == 0) {
+                    return Err(DecodeError::StringNul(i));
+                }
+                Ok(from_utf8(string)?)
+            }
+            Some((b, _)) => Err(DecodeError::StringNotNull(*b)),
+            None => Err(DecodeError::NotEnoughBytes(
+                end,
+                Decoder::checked_add(end, 1)?,
+            )),
+        }
+    }
+
+    fn unix_fd(&self, start: usize, end: usize) -> DecodeResult<Value> {
+        let handle = decode_number!(self, start, end, i32);
+        let i = handle as u32;
+        if let Some(unix_fds) = self.unix_fds {
+            if unix_fds <= i {
+                return Err(DecodeError::UnixFdIndex(i, unix_fds));
+            }
+        }
+        #[cfg(target_family = "unix")]
+        if let Some(fds) = self.fds {
+            let i = i as usize;
+            return if let Some(fd) = fds.get(i) {
+                let fd = fd
+                    .try_clone()
+                    .map_err(|e| DecodeError::DuplicateFd(e.kind()))?;
+                Ok(Value::UnixFD(fd))
+            } else {
+                Err(DecodeError::NotEnoughFds(fds.len(), i))
+            };
+        }
+        Ok(Value::UnixFdIndex(i))
+    }
+
+    /// Decode a signature and check the depth of the types.
+    fn signature(&self, start: usize, end: usize) -> DecodeResult<Vec<Type>> {
+        let signature = Type::from_string_to_signature(self.d_str(start, end)?)?;
+        for type_ in &signature {
+            self.options.check_depth(type_, 0, 0, 0)?;
+        }
+        Ok(signature)
+    }
+
+    fn variant(
+        &mut self,
+        start: usize,
+        end: usize,
+        mut variant_depth: u8,
+        container_depth: u8,
+    ) -> DecodeResult<Value> {
+        let container_depth = self.container_depth(container_depth)?;
+        variant_depth += 1;
+        if self.options.maximum_variant_depth < variant_depth {
+            return Err(DecodeError::VariantDepth(variant_depth));
+        }
+
+        // The value is followed by a null byte and the type string.
+        let bytes = self.get(start, end)?;
+        let separator = match bytes.iter().rposition(|b| *b == 0) {
+            Some(separator) => separator,
+            None => return Err(DecodeError::VariantSeparator),
+        };
+        let type_ = Type::try_from(&bytes[separator + 1..])?;
+        self.options.check_depth(&type_, 0, 0, 0)?;
+        self.path.push(DecodePathElement::Variant);
+        let value = self.value(
+            &type_,
+            start,
+            start + separator,
+            variant_depth,
+            container_depth,
+        )?;
+        self.path.pop();
+        Ok(Value::Variant(Box::new(value)))
+    }
+
+    /// Decode a maybe value. A value with a variable size is followed by a null byte.
+    fn maybe(
+        &mut self,
+        type_: &Type,
+        start: usize,
+        end: usize,
+        variant_depth: u8,
+        container_depth: u8,
+    ) -> DecodeResult<Value> {
+        let container_depth = self.container_depth(container_depth)?;
+        let value = if start == end {
+            None
+        } else if type_.get_gvariant_fixed_size().is_some() {
+            Some(self.value(type_, start, end, variant_depth, container_depth)?)
+        } else {
+            match self.get(start, end)?.last() {
+                Some(0) => {
+                    Some(self.value(type_, start, end - 1, variant_depth, container_depth)?)
+                }
+                Some(b) => return Err(DecodeError::Padding(*b)),
+                None => None,
+            }
+        };
+        let maybe = Maybe {
+            type_: type_.clone(),
+            value: value.map(Box::new),
+        };
+        Ok(Value::Maybe(maybe))
+    }
+
+    #[allow(clippy::manual_is_multiple_of)]
+    pub(super) fn array(
+        &mut self,
+        type_: &Type,
+        start: usize,
+        end: usize,
+        variant_depth: u8,
+        container_depth: u8,
+    ) -> DecodeResult<Vec<Value>> {
+        let container_depth = self.container_depth(container_depth)?;
+        let size = end - start;
+        if self.options.maximum_array_length < size {
+            let size = u32::try_from(size).unwrap_or(u32::MAX);
+            return Err(DecodeError::ArrayTooBig(size));
+        }
+
+        let mut values = Vec::new();
+        self.path.push(DecodePathElement::ArrayElement(0));
+        if let Some(fixed_size) = type_.get_gvariant_fixed_size() {
+            // The elements are stored consecutively without framing offsets.
+            if size % fixed_size != 0 {
+                return Err(DecodeError::ArrayInvalidLength(size, fixed_size));
+            }
+            for offset in (start..end).step_by(fixed_size) {
+                self.set_array_element(values.len());
+                let value = self.value(
+                    type_,
+                    offset,
+                    offset + fixed_size,
+                    variant_depth,
+                    container_depth,
+                )?;
+                values.push(value);
+            }
+        } else if size != 0 {
+            // The last framing offset points to the start of the framing offsets.
+            let offset_size = gvariant_offset_size(size);
+            let offsets_end = end - offset_size;
+            let offsets_start =
+                Decoder::checked_add(start, self.framing_offset(offsets_end, offset_size)?)?;
+            if offsets_end < offsets_start || (end - offsets_start) % offset_size != 0 {
+                return Err(DecodeError::FramingOffset(offsets_start - start));
+            }
+
+            let alignment = type_.get_gvariant_alignment();
+            let mut offset = start;
+            for framing_offset in (offsets_start..end).step_by(offset_size) {
+                offset = GVariantDecoder::algin(start, offset, alignment);
+                let element_end =
+                    Decoder::checked_add(start, self.framing_offset(framing_offset, offset_size)?)?;
+                if element_end < offset || offsets_start < element_end {
+                    return Err(DecodeError::FramingOffset(element_end - start));
+                }
+                self.set_array_element(values.len());
+                let value =
+                    self.value(type_, offset, element_end, variant_depth, container_depth)?;
+                values.push(value);
+                offset = element_end;
+            }
+        }
+        self.path.pop();
+        Ok(values)
+    }
+
+    /// Replace the last element of the path by the array element at index `i`.
+    #[inline]
+    fn set_array_element(&mut self, i: usize) {
+        if let Some(path_element) = self.path.last_mut() {
+            *path_element = DecodePathElement::ArrayElement(i);
+        }
+    }
+
+    /// Decode the members of a struct or a dict entry. The path of each member is given by
+    /// `path_element`.
+    pub(super) fn struct_values(
+        &mut self,
+        types: &[&Type],
+        start: usize,
+        end: usize,
+        variant_depth: u8,
+        container_depth: u8,
+        path_element: fn(usize) -> DecodePathElement,
+    ) -> DecodeResult<Vec<Value>> {
+        let size = end - start;
+        if types.is_empty() {
+            // The unit type is encoded as a single null byte.
+            return if size == 1 {
+                Ok(Vec::new())
+            } else {
+                Err(DecodeError::FixedSize(1, size))
+            };
+        }
+
+        // The framing offsets of all members with a variable size, except the last one, are
+        // stored in reverse order at the end of the struct.
+        let offset_size = gvariant_offset_size(size);
+        let mut offsets_end = end;
+        let mut offset = start;
+        let mut is_fixed = true;
+        let mut values = Vec::with_capacity(types.len());
+        for (i, type_) in types.iter().enumerate() {
+            offset = GVariantDecoder::algin(start, offset, type_.get_gvariant_alignment());
+            let member_end = match type_.get_gvariant_fixed_size() {
+                Some(fixed_size) => Decoder::checked_add(offset, fixed_size)?,
+                None => {
+                    is_fixed = false;
+                    if i + 1 == types.len() {
+                        offsets_end
+                    } else {
+                        if offsets_end < start + offset_size {
+                            return Err(DecodeError::NotEnoughBytes(size, offset_size));
+                        }
+                        offsets_end -= offset_size;
+                        Decoder::checked_add(start, self.framing_offset(offsets_end, offset_size)?)?
+                    }
+                }
+            };
+            if member_end < offset || offsets_end < member_end {
+                return Err(DecodeError::FramingOffset(member_end - start));
+            }
+            self.path.push(path_element(i));
+            let value = self.value(type_, offset, member_end, variant_depth, container_depth)?;
+            self.path.pop();
+            values.push(value);
+            offset = member_end;
+        }
+
+        // A struct with a fixed size is padded to its alignment. Otherwise, the last member ends
+        // at the framing offsets.
+        if !is_fixed && offset != offsets_end {
+            return Err(DecodeError::FramingOffset(offset - start));
+        }
+        Ok(values)
+    }
+
+    /// Decode the value of the type `type_` from the bytes between `start` and `end`.
+    pub(crate) fn value(
+        &mut self,
+        type_: &Type,
+        start: usize,
+        end: usize,
+        variant_depth: u8,
+        container_depth: u8,
+    ) -> DecodeResult<Value> {
+        self.offset = start;
+        if let Some(fixed_size) = type_.get_gvariant_fixed_size() {
+            let size = end - start;
+            if size != fixed_size {
+                return Err(DecodeError::FixedSize(fixed_size, size));
+            }
+        }
+
+        match type_ {
+            Type::Byte => Ok(Value::Byte(self.get(start, end)?[0])),
+            Type::Boolean => match self.get(start, end)?[0] {
+                0 => Ok(Value::Boolean(false)),
+                1 => Ok(Value::Boolean(true)),
+                b => Err(DecodeError::InvalidBoolean(b as u32)),
+            },
+            Type::Int16 => Ok(Value::Int16(decode_number!(self, start, end, i16))),
+            Type::Uint16 => Ok(Value::Uint16(decode_number!(self, start, end, u16))),
+            Type::Int32 => Ok(Value::Int32(decode_number!(self, start, end, i32))),
+            Type::Uint32 => Ok(Value::Uint32(decode_number!(self, start, end, u32))),
+            Type::Int64 => Ok(Value::Int64(decode_number!(self, start, end, i64))),
+            Type::Uint64 => Ok(Value::Uint64(decode_number!(self, start, end, u64))),
+            Type::Double => Ok(Value::Double(decode_number!(self, start, end, f64))),
+            Type::UnixFD => self.unix_fd(start, end),
+            Type::String => Ok(Value::String(self.d_str(start, end)?.to_string())),
+            Type::ObjectPath => {
+                let object_path = ObjectPath::try_from(self.d_str(start, end)?)?;
+                Ok(Value::ObjectPath(object_path))
+            }
+            Type::Signature => Ok(Value::Signature(self.signature(start, end)?)),
+            Type::Variant => self.variant(start, end, variant_depth, container_depth),
+            Type::Array(type_) => {
+                let array = self.array(type_, start, end, variant_depth, container_depth)?;
+                Ok(Value::Array(Array {
+                    type_: type_.as_ref().clone(),
+                    array,
+                }))
+            }
+            Type::Struct(types) => {
+                let container_depth = self.container_depth(container_depth)?;
+                let types: Vec<&Type> = types.iter().collect();
+                let values = self.struct_values(
+                    &types,
+                    start,
+                    end,
+                    variant_depth,
+                    container_depth,
+                    DecodePathElement::StructField,
+                )?;
+                Ok(Value::Struct(Struct(values)))
+            }
+            Type::DictEntry(types) => {
+                let container_depth = self.container_depth(container_depth)?;
+                let mut values = self.struct_values(
+                    &[&types.0, &types.1],
+                    start,
+                    end,
+                    variant_depth,
+                    container_depth,
+                    dict_entry_path_element,
+                )?;
+                let value = values.pop().unwrap();
+                let key = values.pop().unwrap();
+                Ok(Value::DictEntry(Box::new((key, value))))
+            }
+            Type::Maybe(type_) => self.maybe(type_, start, end, variant_depth, container_depth),
+        }
+    }
+}
+
+/// Returns the path element of the key or the value of a dict entry.
+fn dict_entry_path_element(i: usize) -> DecodePathElement {
+    if i == 0 {
+        DecodePathElement::DictKey
+    } else {
+        DecodePathElement::DictValue
+    }
+}
+
+impl Value {
+    /// Decode a [`Value`] of the type `type_` from bytes in the [GVariant format], for example a
+    /// value stored by dconf. The bytes have to contain exactly the value.
+    ///
+    /// The handles are decoded to [`Value::UnixFdIndex`], because there are no FDs, which are
+    /// referenced by the handles.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::value::{Type, Value};
+    /// let value = Value::decode_gvariant(b"text\0", &Type::String, true).unwrap();
+    /// assert_eq!(value, Value::String("text".to_string()));
+    /// ```
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub fn decode_gvariant(bytes: &[u8], type_: &Type, is_le: bool) -> LocatedDecodeResult<Value> {
+        Value::decode_gvariant_with_options(bytes, type_, is_le, DecodeOptions::default())
+    }
+
+    /// Decode a [`Value`] of the type `type_` from bytes in the [GVariant format] with the given
+    /// limits.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub fn decode_gvariant_with_options(
+        bytes: &[u8],
+        type_: &Type,
+        is_le: bool,
+        options: DecodeOptions,
+    ) -> LocatedDecodeResult<Value> {
+        let mut decoder = GVariantDecoder::new(bytes, is_le, options);
+        let result = options
+            .check_depth(type_, 0, 0, 0)
+            .and_then(|_| decoder.value(type_, 0, bytes.len(), 0, 0));
+        result.map_err(|e| decoder.located(e))
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    decode::{DecodeError, DecodeResult, Decoder},
-    message::{MessageFlags, MessageHeader, MessageHeaderFields, MessageType},
+    decode::{DecodeError, DecodePathElement, DecodeResult, Decoder},
+    message::{
+        MessageFlags, MessageHeader, MessageHeaderFields, MessageType, MAJOR_PROTOCOL_VERSION,
+    },
     value::Type,
 };
 #[cfg(test)]
@@ -12,15 +14,24 @@ impl<'a> Decoder<'a> {
         let b = self.u_8()?;
         match MessageType::try_from(b) {
             Ok(message_type) => Ok(message_type),
-            Err(message_type) => Err(DecodeError::MessageType(message_type)),
+            // 0 is an invalid type, all other types are unknown and have to be ignored.
+            Err(0) => Err(DecodeError::MessageType(0)),
+            Err(message_type) => Err(DecodeError::UnknownMessageType(message_type)),
         }
     }
 
     fn message_header_flags(&mut self) -> DecodeResult<MessageFlags> {
         let b = self.u_8()?;
-        match MessageFlags::from_bits(b) {
-            Some(message_flags) => Ok(message_flags),
-            None => Err(DecodeError::MessageFlags(b)),
+        // Unknown flags have to be ignored, but they are kept, so they can be encoded again.
+        // SAFETY: `MessageFlags` is only a set of bits, every `u8` is a valid value.
+        let message_flags = unsafe { MessageFlags::from_bits_unchecked(b) };
+        Ok(message_flags)
+    }
+
+    fn message_header_version(&mut self) -> DecodeResult<u8> {
+        match self.u_8()? {
+            MAJOR_PROTOCOL_VERSION => Ok(MAJOR_PROTOCOL_VERSION),
+            version => Err(DecodeError::UnsupportedVersion(version)),
         }
     }
 
@@ -29,13 +40,13 @@ impl<'a> Decoder<'a> {
         is_le: bool,
     ) -> DecodeResult<(Option<Vec<Type>>, MessageHeaderFields)> {
         let signature = Type::Struct(vec![Type::Byte, Type::Variant]);
-        let array = self.d_array(is_le, 0, &signature)?;
+        let array = self.d_array(is_le, 0, 0, &signature)?;
         let mut fields = MessageHeaderFields::try_from(array)?;
         let body_signature = fields.signature.take();
         Ok((body_signature, fields))
     }
 
-    fn message_header_is_le(&mut self) -> DecodeResult<bool> {
+    pub(crate) fn message_header_is_le(&mut self) -> DecodeResult<bool> {
         match self.u_8()? {
             0x6c => Ok(true),
             0x42 => Ok(false),
@@ -43,7 +54,113 @@ impl<'a> Decoder<'a> {
         }
     }
 
+    /// Decode the fixed part of the message header and the length of the header fields array and
+    /// returns the length of the whole message (header, padding and body).
+    pub(crate) fn message_length(&mut self) -> DecodeResult<usize> {
+        let is_le = self.message_header_is_le()?;
+
+        // Skip the message type, the message flags and the major protocol version.
+        self.read(3)?;
+
+        // Get the length in bytes of the message body.
+        let body_length = self.u_32(is_le)?;
+
+        // Skip the serial number of this message.
+        self.u_32(is_le)?;
+
+        // Get the length in bytes of the header fields array.
+        let fields_length = self.u_32(is_le)?;
+
+        // The body starts at an 8-byte boundary.
+        let mut header_length = Decoder::checked_add(self.offset, fields_length as usize)?;
+        let remain = header_length % 8;
+        if remain != 0 {
+            header_length = Decoder::checked_add(header_length, 8 - remain)?;
+        }
+
+        Decoder::checked_add(header_length, body_length as usize)
+    }
+
+    /// Check the length of the whole message, which is declared in the header, against the
+    /// maximum message length.
+    fn message_check_length(&mut self) -> DecodeResult<()> {
+        let start = self.offset;
+        let length = self.message_length()? - start;
+        self.offset = start;
+        if length <= self.options.maximum_message_length {
+            Ok(())
+        } else {
+            Err(DecodeError::MessageTooLong(length))
+        }
+    }
+
+    /// Decode the header of a message without building the header fields.
+    ///
+    /// Returns the endianness, the number, the start and the end offset of every header field and
+    /// the start and the end offset of the body.
+    pub(crate) fn message_header_field_offsets(
+        &mut self,
+    ) -> DecodeResult<(bool, Vec<(u8, usize, usize)>, (usize, usize))> {
+        self.message_check_length()?;
+
+        let is_le = self.message_header_is_le()?;
+
+        // Skip the message type and the message flags.
+        self.read(2)?;
+
+        // Check the major protocol version.
+        self.message_header_version()?;
+
+        // Get the length in bytes of the message body.
+        let body_length = self.u_32(is_le)?;
+
+        // Skip the serial number of this message.
+        self.u_32(is_le)?;
+
+        // Get the offsets of the header fields.
+        let fields_length = self.u_32(is_le)?;
+        self.algin(8)?;
+        let end = Decoder::checked_add(self.offset, fields_length as usize)?;
+        let mut fields = Vec::new();
+        while self.offset < end {
+            self.algin(8)?;
+            let start = self.offset;
+            let number = self.u_8()?;
+            // The variant is nested in the array and the struct of the header fields.
+            self.variant(is_le, 0, 2)?;
+            fields.push((number, start, self.offset));
+        }
+        if self.offset != end {
+            return Err(DecodeError::ArrayInvalidLength(self.offset, end));
+        }
+
+        // The body starts at an 8-byte boundary.
+        self.algin(8)?;
+        let body_start = self.offset;
+        self.read(body_length as usize)?;
+
+        Ok((is_le, fields, (body_start, self.offset)))
+    }
+
+    /// Decode the number of FDs, which is declared in the header fields, without checking the
+    /// message type. This is used to skip messages with an unknown type.
+    #[cfg(target_family = "unix")]
+    pub(crate) fn message_unix_fds(&mut self) -> DecodeResult<u32> {
+        let is_le = self.message_header_is_le()?;
+
+        // Skip the message type, the message flags, the major protocol version, the length of the
+        // body and the serial number.
+        self.read(11)?;
+
+        let (_, fields) = self.message_header_fields(is_le)?;
+        Ok(fields.unix_fds.unwrap_or(0))
+    }
+
     pub fn message_header(&mut self) -> DecodeResult<(MessageHeader, Option<(u32, Vec<Type>)>)> {
+        self.path.push(DecodePathElement::Header);
+
+        self.message_check_length()?;
+
         let is_le = self.message_header_is_le()?;
 
         // Get the message type.
@@ -53,7 +170,7 @@ impl<'a> Decoder<'a> {
         let message_flags = self.message_header_flags()?;
 
         // Get the major protocol version.
-        let version = self.u_8()?;
+        let version = self.message_header_version()?;
 
         // Get the length in bytes of the message body.
         let body_length = self.u_32(is_le)?;
@@ -67,57 +184,79 @@ impl<'a> Decoder<'a> {
         let message_header =
             MessageHeader::new(is_le, message_type, message_flags, version, serial, headers)?;
 
-        if body_length == 0 {
+        let body = if body_length == 0 {
             match body_signature {
                 Some(signature) => {
                     if signature.is_empty() {
-                        Ok((message_header, None))
+                        None
                     } else {
-                        Err(DecodeError::BodyLengthZero(signature))
+                        return Err(DecodeError::BodyLengthZero(signature));
                     }
                 }
-                None => Ok((message_header, None)),
+                None => None,
             }
         } else {
             match body_signature {
-                Some(signature) => Ok((message_header, Some((body_length, signature)))),
-                None => Err(DecodeError::BodySignatureMissing(body_length)),
+                Some(signature) => Some((body_length, signature)),
+                None => return Err(DecodeError::BodySignatureMissing(body_length)),
             }
-        }
+        };
+
+        self.path.pop();
+        Ok((message_header, body))
     }
 }
 
 #[test]
 fn message_header_type_error() {
+    let b = Bytes::from_static(b"\x00");
+    let mut decoder = Decoder::new(&b);
+    assert_eq!(
+        decoder.message_header_type(),
+        Err(DecodeError::MessageType(0x00))
+    );
+}
+
+#[test]
+fn message_header_type_unknown() {
     let b = Bytes::from_static(b"\x11");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
         decoder.message_header_type(),
-        Err(DecodeError::MessageType(0x11))
+        Err(DecodeError::UnknownMessageType(0x11))
     );
 }
 
 #[test]
-fn message_header_flags_error() {
+fn message_header_flags_unknown() {
     let b = Bytes::from_static(b"\xff");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
+    let message_flags = decoder.message_header_flags().unwrap();
+    assert!(message_flags.contains(MessageFlags::all()));
+    assert_eq!(message_flags.bits(), 0xff);
+}
+
+#[test]
+fn message_header_version_error() {
+    let b = Bytes::from_static(b"\x02");
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
-        decoder.message_header_flags(),
-        Err(DecodeError::MessageFlags(0xff))
+        decoder.message_header_version(),
+        Err(DecodeError::UnsupportedVersion(0x02))
     );
 }
 
 #[test]
 fn message_header_is_le_big_endian() {
     let b = Bytes::from_static(b"B");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(decoder.message_header_is_le(), Ok(false));
 }
 
 #[test]
 fn message_header_is_le_error() {
     let b = Bytes::from_static(b"\xff");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
         decoder.message_header_is_le(),
         Err(DecodeError::Endianness(0xff))
@@ -131,7 +270,7 @@ fn message_body_signature() {
     \x00\x00\x00\x6f\x72\x67\x2e\x65\x78\x61\x6d\x70\x6c\x65\x2e\x65\x72\x72\x6f\x72\x00\x00\x00\
     \x00\x00\x00\x00\x05\x01\x75\x00\x0a\x00\x00\x00\x08\x01\x67\x00\x00\x00",
     );
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert!(decoder.message_header().is_ok(),);
 }
 
@@ -145,7 +284,7 @@ fn message_body_signature_error_1() {
     \x00\x00\x00\x06\x01\x73\x00\x13\x00\x00\x00\x64\x65\x73\x74\x69\x6e\x61\x74\x69\x6f\x6e\x2e\
     \x61\x64\x64\x72\x65\x73\x73\x00\x00\x00\x00\x00",
     );
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
         decoder.message_header(),
         Err(DecodeError::BodySignatureMissing(1))
@@ -159,9 +298,45 @@ fn message_body_signature_error_2() {
     \x00\x00\x00\x6f\x72\x67\x2e\x65\x78\x61\x6d\x70\x6c\x65\x2e\x65\x72\x72\x6f\x72\x00\x00\x00\
     \x00\x00\x00\x00\x05\x01\x75\x00\x0a\x00\x00\x00\x08\x01\x67\x00\x01\x73\x00",
     );
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
         decoder.message_header(),
         Err(DecodeError::BodyLengthZero(vec![Type::String]))
     );
 }
+
+#[test]
+fn message_length() {
+    let b = Bytes::from_static(b"\x6c\x01\x00\x01\x18\x00\x00\x00\x00\x00\x00\x00\x70\x00\x00\x00");
+    let mut decoder = Decoder::new(&b);
+    assert_eq!(decoder.message_length(), Ok(16 + 0x70 + 0x18));
+}
+
+#[test]
+fn message_length_padding() {
+    let b = Bytes::from_static(b"\x42\x02\x01\x01\x00\x00\x00\x04\x00\x00\x00\x02\x00\x00\x00\x3d");
+    let mut decoder = Decoder::new(&b);
+    assert_eq!(decoder.message_length(), Ok(16 + 0x3d + 3 + 4));
+}
+
+#[test]
+fn message_length_integer_overflow() {
+    let b = Bytes::from_static(b"\x6c\x01\x00\x01\xff\xff\xff\xff\x00\x00\x00\x00\xff\xff\xff\xff");
+    let mut decoder = Decoder::new(&b);
+    let result = decoder.message_length();
+    if usize::BITS <= 32 {
+        assert!(matches!(result, Err(DecodeError::IntegerOverflow(_, _))));
+    } else {
+        assert_eq!(result, Ok(16 + 0xffffffff + 1 + 0xffffffff));
+    }
+}
+
+#[test]
+fn message_header_too_long() {
+    let b = Bytes::from_static(b"l\x04\x01\x01\x00\x00\x00\x08\x01\x00\x00\x00\x00\x00\x00\x00");
+    let mut decoder = Decoder::new(&b);
+    assert_eq!(
+        decoder.message_header(),
+        Err(DecodeError::MessageTooLong(0x08000000 + 16))
+    );
+}
@@ -0,0 +1,259 @@
+#[cfg(target_family = "unix")]
+use crate::decode::LocatedDecodeError;
+use crate::decode::{DecodeError, DecodePathElement, DecodeResult, Decoder, LocatedDecodeResult};
+use crate::message::{LazyMessage, LazyMessageArgs, Message, MessageHeader};
+#[cfg(test)]
+use crate::message::{MessageFlags, MessageHeaderFields, MessageType};
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+use crate::value::{Type, Value, ValueRef};
+use bytes::Bytes;
+#[cfg(test)]
+use std::convert::TryInto;
+
+impl<'a> Decoder<'a> {
+    /// Decode the header of a message and skip the body. Returns the header, the signature of the
+    /// body and the offset, where the body starts.
+    fn lazy_message(&mut self) -> DecodeResult<(MessageHeader, Vec<Type>, usize)> {
+        let (header, body) = self.message_header()?;
+
+        self.algin(8)?;
+
+        let start = self.offset;
+        let signature = match body {
+            Some((body_length, body_signature)) => {
+                self.read(body_length as usize)?;
+                body_signature
+            }
+            None => Vec::new(),
+        };
+
+        Ok((header, signature, start))
+    }
+}
+
+impl LazyMessage {
+    /// Decode only the header of a [`LazyMessage`] and returns the offset. The body is not
+    /// decoded.
+    ///
+    /// # Example
+    /// ```
+    /// # use bytes::Bytes;
+    /// # use dbus_message_parser::message::LazyMessage;
+    /// # use dbus_message_parser::value::Value;
+    /// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+    /// let bytes = Bytes::copy_from_slice(&msg[..]);
+    ///
+    /// let (msg, _) = LazyMessage::decode(bytes).unwrap();
+    /// assert_eq!(msg.get_header().get_reply_serial(), Some(1));
+    /// assert_eq!(msg.decode_body(), Ok(vec![Value::String(":1.98".to_string())]));
+    /// ```
+    pub fn decode(bytes: Bytes) -> LocatedDecodeResult<(LazyMessage, usize)> {
+        let mut decoder = Decoder::new(&bytes);
+        let (header, signature, start) = decoder.lazy_message().map_err(|e| decoder.located(e))?;
+        let offset = decoder.offset;
+        let msg = LazyMessage {
+            header,
+            signature,
+            body: bytes.slice(start..offset),
+            #[cfg(target_family = "unix")]
+            fds: Vec::new(),
+        };
+        Ok((msg, offset))
+    }
+
+    /// Decode only the header of a [`LazyMessage`] and returns the offset and the offset of the
+    /// given FDs. The number of FDs, which belong to the message, is taken from the [`unix_fds`]
+    /// header field and these FDs are duplicated.
+    ///
+    /// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
+    #[cfg(target_family = "unix")]
+    pub fn decode_with_fds(
+        bytes: Bytes,
+        fds: &[UnixFd],
+    ) -> LocatedDecodeResult<(LazyMessage, usize, usize)> {
+        let mut decoder = Decoder::new_with_fds(&bytes, fds);
+        let (header, signature, start) = decoder.lazy_message().map_err(|e| decoder.located(e))?;
+        let offset = decoder.offset;
+        decoder.unix_fds = Some(header.get_unix_fds().unwrap_or(0));
+        let offset_fds = decoder.message_fds()?;
+        let fds = fds[..offset_fds]
+            .iter()
+            .map(UnixFd::try_clone)
+            .collect::<Result<_, _>>()
+            .map_err(|e| {
+                let error = DecodeError::DuplicateFd(e.kind());
+                LocatedDecodeError::new(error, 0, vec![DecodePathElement::Header])
+            })?;
+        let msg = LazyMessage {
+            header,
+            signature,
+            body: bytes.slice(start..offset),
+            fds,
+        };
+        Ok((msg, offset, offset_fds))
+    }
+
+    fn body_decoder(&self) -> Decoder<'_> {
+        // Without FDs only the indices of the FDs are decoded.
+        #[cfg(target_family = "unix")]
+        let mut decoder = if self.fds.is_empty() {
+            Decoder::new(&self.body)
+        } else {
+            Decoder::new_with_fds(&self.body, &self.fds)
+        };
+        #[cfg(not(target_family = "unix"))]
+        let mut decoder = Decoder::new(&self.body);
+        decoder.unix_fds = Some(self.header.get_unix_fds().unwrap_or(0));
+        decoder
+    }
+
+    fn check_body_length(&self, decoder: &Decoder<'_>) -> DecodeResult<()> {
+        let body_length = self.body.len();
+        if body_length == decoder.offset {
+            Ok(())
+        } else {
+            Err(DecodeError::BodyLength(body_length, decoder.offset))
+        }
+    }
+
+    /// Decode the whole body.
+    ///
+    /// The offset of the error is relative to the start of the body.
+    pub fn decode_body(&self) -> LocatedDecodeResult<Vec<Value>> {
+        let mut decoder = self.body_decoder();
+        let body = decoder
+            .values(
+                self.header.is_le,
+                0,
+                0,
+                &self.signature,
+                DecodePathElement::BodyArgument,
+            )
+            .map_err(|e| decoder.located(e))?;
+        self.check_body_length(&decoder)
+            .map_err(|e| decoder.located(e))?;
+        Ok(body)
+    }
+
+    /// Decode the whole body without copying the strings.
+    ///
+    /// The offset of the error is relative to the start of the body.
+    pub fn decode_body_ref(&self) -> LocatedDecodeResult<Vec<ValueRef<'_>>> {
+        let mut decoder = self.body_decoder();
+        let body = decoder
+            .values_ref(
+                self.header.is_le,
+                0,
+                0,
+                &self.signature,
+                DecodePathElement::BodyArgument,
+            )
+            .map_err(|e| decoder.located(e))?;
+        self.check_body_length(&decoder)
+            .map_err(|e| decoder.located(e))?;
+        Ok(body)
+    }
+
+    /// Returns an iterator, which decodes the body argument by argument.
+    ///
+    /// The offset of an error is relative to the start of the body.
+    ///
+    /// # Example
+    /// ```
+    /// # use bytes::Bytes;
+    /// # use dbus_message_parser::message::LazyMessage;
+    /// # use dbus_message_parser::value::Value;
+    /// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+    /// let bytes = Bytes::copy_from_slice(&msg[..]);
+    ///
+    /// let (msg, _) = LazyMessage::decode(bytes).unwrap();
+    /// let mut args = msg.args();
+    /// assert_eq!(args.next(), Some(Ok(Value::String(":1.98".to_string()))));
+    /// assert_eq!(args.next(), None);
+    /// ```
+    pub fn args(&self) -> LazyMessageArgs<'_> {
+        LazyMessageArgs {
+            decoder: self.body_decoder(),
+            is_le: self.header.is_le,
+            signature: self.signature.iter().enumerate(),
+            finished: false,
+        }
+    }
+
+    /// Decode the body and convert the [`LazyMessage`] into a [`Message`].
+    pub fn into_message(self) -> LocatedDecodeResult<Message> {
+        let body = self.decode_body()?;
+        Ok(Message::new(self.header, body))
+    }
+}
+
+impl<'a> Iterator for LazyMessageArgs<'a> {
+    type Item = LocatedDecodeResult<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let decoder = &mut self.decoder;
+        match self.signature.next() {
+            Some((i, type_)) => {
+                decoder.path.push(DecodePathElement::BodyArgument(i));
+                let result = decoder.value(self.is_le, 0, 0, type_);
+                let result = result.map_err(|e| decoder.located(e));
+                decoder.path.pop();
+                if result.is_err() {
+                    self.finished = true;
+                }
+                Some(result)
+            }
+            None => {
+                self.finished = true;
+                let body_length = decoder.buf.len();
+                if body_length == decoder.offset {
+                    None
+                } else {
+                    let error = DecodeError::BodyLength(body_length, decoder.offset);
+                    Some(Err(decoder.located(error)))
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn lazy_message_args_body_length() {
+    let header = MessageHeader::new(
+        true,
+        MessageType::Signal,
+        MessageFlags::empty(),
+        1,
+        1,
+        MessageHeaderFields {
+            path: Some("/".try_into().unwrap()),
+            interface: Some("a.b".try_into().unwrap()),
+            member: Some("C".try_into().unwrap()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let msg = LazyMessage {
+        header,
+        signature: vec![Type::Byte],
+        body: Bytes::from_static(b"\x01\x02"),
+        #[cfg(target_family = "unix")]
+        fds: Vec::new(),
+    };
+    let mut args = msg.args();
+    assert_eq!(args.next(), Some(Ok(Value::Byte(1))));
+    assert_eq!(
+        args.next().unwrap().map_err(DecodeError::from),
+        Err(DecodeError::BodyLength(2, 1))
+    );
+    assert_eq!(args.next(), None);
+    assert_eq!(
+        msg.decode_body().map_err(DecodeError::from),
+        Err(DecodeError::BodyLength(2, 1))
+    );
+}
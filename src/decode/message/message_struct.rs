@@ -1,9 +1,12 @@
-use crate::decode::{DecodeError, DecodeResult, Decoder};
-use crate::message::Message;
-use crate::value::{Type, Value};
-use bytes::Bytes;
+use crate::decode::{
+    DecodeError, DecodeOptions, DecodePathElement, DecodeResult, Decoder, LocatedDecodeError,
+    LocatedDecodeResult,
+};
+use crate::message::{Message, MessageHeader, MINIMUM_HEADER_LENGTH};
 #[cfg(target_family = "unix")]
-use std::os::unix::io::RawFd;
+use crate::value::UnixFd;
+use crate::value::{Type, Value, ValueRef};
+use bytes::Bytes;
 
 impl<'a> Decoder<'a> {
     fn message_body(
@@ -13,7 +16,22 @@ impl<'a> Decoder<'a> {
         signature: &[Type],
     ) -> DecodeResult<Vec<Value>> {
         let end = Decoder::<'a>::checked_add(self.offset, length as usize)?;
-        let body = self.values(is_le, 0, signature)?;
+        let body = self.values(is_le, 0, 0, signature, DecodePathElement::BodyArgument)?;
+        if end == self.offset {
+            Ok(body)
+        } else {
+            Err(DecodeError::BodyLength(end, self.offset))
+        }
+    }
+
+    fn message_body_ref(
+        &mut self,
+        is_le: bool,
+        length: u32,
+        signature: &[Type],
+    ) -> DecodeResult<Vec<ValueRef<'a>>> {
+        let end = Decoder::<'a>::checked_add(self.offset, length as usize)?;
+        let body = self.values_ref(is_le, 0, 0, signature, DecodePathElement::BodyArgument)?;
         if end == self.offset {
             Ok(body)
         } else {
@@ -36,6 +54,7 @@ impl<'a> Decoder<'a> {
     /// ```
     pub(crate) fn message(&mut self) -> DecodeResult<Message> {
         let (header, body) = self.message_header()?;
+        self.unix_fds = Some(header.get_unix_fds().unwrap_or(0));
 
         self.algin(8)?;
 
@@ -48,36 +67,183 @@ impl<'a> Decoder<'a> {
 
         Ok(Message::new(header, body))
     }
+
+    /// Decode a byte array to a [`MessageHeader`] and a body, which references the byte array.
+    ///
+    /// [`MessageHeader`]: crate::message::MessageHeader
+    pub(crate) fn message_ref(&mut self) -> DecodeResult<(MessageHeader, Vec<ValueRef<'a>>)> {
+        let (header, body) = self.message_header()?;
+        self.unix_fds = Some(header.get_unix_fds().unwrap_or(0));
+
+        self.algin(8)?;
+
+        let body = match body {
+            Some((body_length, body_signature)) => {
+                self.message_body_ref(header.is_le, body_length, &body_signature)?
+            }
+            None => Vec::new(),
+        };
+
+        Ok((header, body))
+    }
+
+    /// Returns the number of FDs, which belong to the decoded message. This is the number, which is
+    /// declared in the `UNIX_FDS` header field, even if the body does not reference all of them.
+    #[cfg(target_family = "unix")]
+    pub(super) fn message_fds(&self) -> LocatedDecodeResult<usize> {
+        let unix_fds = self.unix_fds.unwrap_or(0) as usize;
+        let fds_len = self.fds.map_or(0, <[UnixFd]>::len);
+        if unix_fds <= fds_len {
+            Ok(unix_fds)
+        } else {
+            let error = DecodeError::NotEnoughFds(fds_len, unix_fds);
+            Err(LocatedDecodeError::new(
+                error,
+                0,
+                vec![DecodePathElement::Header],
+            ))
+        }
+    }
 }
 
 impl Message {
+    /// Returns the length of the encoded [`Message`], which begins at the start of `bytes`.
+    ///
+    /// Only the first [`MINIMUM_HEADER_LENGTH`] bytes are needed to determine the length of the
+    /// header, the padding and the body. If `bytes` is shorter than that, a
+    /// [`DecodeError::NotEnoughBytes`] is returned, which contains the number of available and the
+    /// number of needed bytes.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::decode::DecodeError;
+    /// # use dbus_message_parser::message::Message;
+    /// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+    ///
+    /// assert_eq!(
+    ///     Message::decode_length(&msg[..10]).map_err(DecodeError::from),
+    ///     Err(DecodeError::NotEnoughBytes(10, 16))
+    /// );
+    /// assert_eq!(Message::decode_length(&msg[..16]), Ok(msg.len()));
+    /// ```
+    ///
+    /// [`MINIMUM_HEADER_LENGTH`]: crate::message::MINIMUM_HEADER_LENGTH
+    pub fn decode_length(bytes: &[u8]) -> LocatedDecodeResult<usize> {
+        let bytes_len = bytes.len();
+        if bytes_len < MINIMUM_HEADER_LENGTH {
+            let error = DecodeError::NotEnoughBytes(bytes_len, MINIMUM_HEADER_LENGTH);
+            return Err(LocatedDecodeError::new(
+                error,
+                0,
+                vec![DecodePathElement::Header],
+            ));
+        }
+
+        let mut decoder = Decoder::new(&bytes[..MINIMUM_HEADER_LENGTH]);
+        decoder.path.push(DecodePathElement::Header);
+        decoder.message_length().map_err(|e| decoder.located(e))
+    }
+
     /// The decode a [`Message`] and returns the offset.
-    pub fn decode(bytes: Bytes) -> DecodeResult<(Message, usize)> {
-        let mut decoder = Decoder::new(bytes);
-        let msg = decoder.message()?;
+    ///
+    /// The error contains the location, where the [`Message`] could not be decoded.
+    pub fn decode(bytes: Bytes) -> LocatedDecodeResult<(Message, usize)> {
+        Message::decode_with_options(bytes, DecodeOptions::default())
+    }
+
+    /// The decode a [`Message`] with the given limits and returns the offset.
+    pub fn decode_with_options(
+        bytes: Bytes,
+        options: DecodeOptions,
+    ) -> LocatedDecodeResult<(Message, usize)> {
+        let mut decoder = Decoder::with_options(&bytes, options);
+        let msg = decoder.message().map_err(|e| decoder.located(e))?;
         let offset = decoder.offset;
         Ok((msg, offset))
     }
 
-    /// The decode a [`Message`] and returns the offset and the offset of the given FDs.
+    /// The decode a [`Message`] and returns the offset and the offset of the given FDs. The FDs,
+    /// which are referenced by the body, are duplicated.
+    ///
+    /// The number of FDs, which belong to the [`Message`], is taken from the [`unix_fds`] header
+    /// field, so the offset of the given FDs includes FDs, which are not referenced by the body.
+    /// An index of a FD in the body has to be smaller than this number, otherwise a
+    /// [`DecodeError::UnixFdIndex`] is returned.
+    ///
+    /// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
     #[cfg(target_family = "unix")]
-    pub fn decode_with_fds(bytes: Bytes, fds: &[RawFd]) -> DecodeResult<(Message, usize, usize)> {
-        let mut decoder = Decoder::new_with_fds(bytes, fds);
-        let msg = decoder.message()?;
+    pub fn decode_with_fds(
+        bytes: Bytes,
+        fds: &[UnixFd],
+    ) -> LocatedDecodeResult<(Message, usize, usize)> {
+        Message::decode_with_fds_and_options(bytes, fds, DecodeOptions::default())
+    }
+
+    /// The decode a [`Message`] with the given limits and returns the offset and the offset of the
+    /// given FDs.
+    #[cfg(target_family = "unix")]
+    pub fn decode_with_fds_and_options(
+        bytes: Bytes,
+        fds: &[UnixFd],
+        options: DecodeOptions,
+    ) -> LocatedDecodeResult<(Message, usize, usize)> {
+        let mut decoder = Decoder::with_fds_and_options(&bytes, fds, options);
+        let msg = decoder.message().map_err(|e| decoder.located(e))?;
         let offset = decoder.offset;
-        let offset_fds = match decoder.offset_fds {
-            Some(offset_fds) => offset_fds + 1,
-            None => 0,
-        };
+        let offset_fds = decoder.message_fds()?;
         Ok((msg, offset, offset_fds))
     }
+
+    /// Decode the [`MessageHeader`] and the body of a [`Message`] without copying the strings of
+    /// the body. Returns the header, the body and the offset.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::message::Message;
+    /// # use dbus_message_parser::value::ValueRef;
+    /// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+    ///
+    /// let (header, body, offset) = Message::decode_ref(&msg[..]).unwrap();
+    /// assert_eq!(body, vec![ValueRef::String(":1.98")]);
+    /// assert_eq!(offset, msg.len());
+    /// ```
+    ///
+    /// [`MessageHeader`]: crate::message::MessageHeader
+    pub fn decode_ref(
+        bytes: &[u8],
+    ) -> LocatedDecodeResult<(MessageHeader, Vec<ValueRef<'_>>, usize)> {
+        let mut decoder = Decoder::new(bytes);
+        let (header, body) = decoder.message_ref().map_err(|e| decoder.located(e))?;
+        let offset = decoder.offset;
+        Ok((header, body, offset))
+    }
+
+    /// Decode the [`MessageHeader`] and the body of a [`Message`] without copying the strings of
+    /// the body. Returns the header, the body, the offset and the offset of the given FDs. The
+    /// body borrows the FDs.
+    ///
+    /// The FDs are checked in the same way as in [`decode_with_fds`].
+    ///
+    /// [`decode_with_fds`]: Message::decode_with_fds
+    /// [`MessageHeader`]: crate::message::MessageHeader
+    #[cfg(target_family = "unix")]
+    pub fn decode_ref_with_fds<'a>(
+        bytes: &'a [u8],
+        fds: &'a [UnixFd],
+    ) -> LocatedDecodeResult<(MessageHeader, Vec<ValueRef<'a>>, usize, usize)> {
+        let mut decoder = Decoder::new_with_fds(bytes, fds);
+        let (header, body) = decoder.message_ref().map_err(|e| decoder.located(e))?;
+        let offset = decoder.offset;
+        let offset_fds = decoder.message_fds()?;
+        Ok((header, body, offset, offset_fds))
+    }
 }
 
 #[test]
 fn message_body_error() {
     let b = Bytes::from_static(b"\xff");
     let type_ = Type::Byte;
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
         decoder.message_body(true, 2, &[type_]),
         Err(DecodeError::BodyLength(2, 1))
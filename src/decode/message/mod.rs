@@ -1,2 +1,3 @@
 mod header;
+mod lazy_message;
 mod message_struct;
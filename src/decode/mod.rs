@@ -1,11 +1,21 @@
 mod decoder;
 mod error;
+mod gvariant;
 mod message;
+mod options;
+mod stream;
+mod swap;
 #[cfg(test)]
 mod tests;
 mod value;
 
 pub(crate) use decoder::Decoder;
-pub use error::{DecodeError, DecodeResult};
+pub use error::{
+    DecodeError, DecodePathElement, DecodeResult, LocatedDecodeError, LocatedDecodeResult,
+};
+pub use options::DecodeOptions;
+pub use stream::MessageStreamDecoder;
 
 pub const MAXIMUM_VARIANT_DEPTH: u8 = 4;
+/// The maximum total depth of nested containers (arrays, structs, dict entries and variants).
+pub const MAXIMUM_CONTAINER_DEPTH: u8 = 64;
@@ -0,0 +1,147 @@
+use crate::{
+    decode::{DecodeError, DecodeResult, MAXIMUM_CONTAINER_DEPTH, MAXIMUM_VARIANT_DEPTH},
+    message::MAXIMUM_MESSAGE_LENGTH,
+    value::{
+        Type, TypeError, MAXIMUM_ARRAY_DEPTH, MAXIMUM_ARRAY_LENGTH, MAXIMUM_DICT_DEPTH,
+        MAXIMUM_STRUCT_DEPTH,
+    },
+};
+
+/// The limits, which are checked during the decoding.
+///
+/// The [`Default`] values are the maxima of the [DBus specification]. The depth limits of the
+/// signatures can only be lowered, because a signature, which exceeds the maxima of the
+/// specification, is always rejected.
+///
+/// # Example
+/// ```
+/// # use bytes::Bytes;
+/// # use dbus_message_parser::decode::{DecodeError, DecodeOptions};
+/// # use dbus_message_parser::message::Message;
+/// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+/// let bytes = Bytes::copy_from_slice(&msg[..]);
+///
+/// let options = DecodeOptions {
+///     maximum_array_length: 16,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     Message::decode_with_options(bytes, options).map_err(DecodeError::from),
+///     Err(DecodeError::ArrayTooBig(61))
+/// );
+/// ```
+///
+/// [DBus specification]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-marshaling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// The maximum length of a message in bytes.
+    pub maximum_message_length: usize,
+    /// The maximum length of an array in bytes.
+    pub maximum_array_length: usize,
+    /// The maximum number of nested variants.
+    pub maximum_variant_depth: u8,
+    /// The maximum total number of nested containers (arrays, structs, dict entries and
+    /// variants) in a value.
+    pub maximum_container_depth: u8,
+    /// The maximum number of nested arrays in a signature.
+    pub maximum_array_depth: u8,
+    /// The maximum number of nested structs in a signature.
+    pub maximum_struct_depth: u8,
+    /// The maximum number of nested dict entries in a signature.
+    pub maximum_dict_depth: u8,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        DecodeOptions {
+            maximum_message_length: MAXIMUM_MESSAGE_LENGTH,
+            maximum_array_length: MAXIMUM_ARRAY_LENGTH,
+            maximum_variant_depth: MAXIMUM_VARIANT_DEPTH,
+            maximum_container_depth: MAXIMUM_CONTAINER_DEPTH,
+            maximum_array_depth: MAXIMUM_ARRAY_DEPTH,
+            maximum_struct_depth: MAXIMUM_STRUCT_DEPTH,
+            maximum_dict_depth: MAXIMUM_DICT_DEPTH,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Check the depth of the arrays, structs and dict entries of a [`Type`].
+    ///
+    /// [`Type`]: crate::value::Type
+    pub(crate) fn check_depth(
+        &self,
+        type_: &Type,
+        array_depth: u8,
+        struct_depth: u8,
+        dict_depth: u8,
+    ) -> DecodeResult<()> {
+        if self.maximum_array_depth < array_depth {
+            return Err(DecodeError::SignatureError(TypeError::ArrayDepth(
+                array_depth,
+            )));
+        }
+
+        if self.maximum_struct_depth < struct_depth {
+            return Err(DecodeError::SignatureError(TypeError::StructDepth(
+                struct_depth,
+            )));
+        }
+
+        if self.maximum_dict_depth < dict_depth {
+            return Err(DecodeError::SignatureError(TypeError::DictDepth(
+                dict_depth,
+            )));
+        }
+
+        match type_ {
+            Type::Array(type_) => {
+                self.check_depth(type_, array_depth + 1, struct_depth, dict_depth)
+            }
+            Type::Struct(types) => {
+                for type_ in types {
+                    self.check_depth(type_, array_depth, struct_depth + 1, dict_depth)?;
+                }
+                Ok(())
+            }
+            Type::DictEntry(types) => {
+                self.check_depth(&types.0, array_depth, struct_depth, dict_depth + 1)?;
+                self.check_depth(&types.1, array_depth, struct_depth, dict_depth + 1)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn check_depth() {
+    let options = DecodeOptions {
+        maximum_array_depth: 1,
+        maximum_struct_depth: 1,
+        maximum_dict_depth: 1,
+        ..Default::default()
+    };
+    let type_ = Type::Array(Box::new(Type::Struct(vec![Type::Byte])));
+    assert_eq!(options.check_depth(&type_, 0, 0, 0), Ok(()));
+
+    let type_ = Type::Array(Box::new(Type::Array(Box::new(Type::Byte))));
+    assert_eq!(
+        options.check_depth(&type_, 0, 0, 0),
+        Err(DecodeError::SignatureError(TypeError::ArrayDepth(2)))
+    );
+
+    let type_ = Type::Struct(vec![Type::Struct(vec![Type::Byte])]);
+    assert_eq!(
+        options.check_depth(&type_, 0, 0, 0),
+        Err(DecodeError::SignatureError(TypeError::StructDepth(2)))
+    );
+
+    let type_ = Type::DictEntry(Box::new((
+        Type::Byte,
+        Type::DictEntry(Box::new((Type::Byte, Type::Byte))),
+    )));
+    assert_eq!(
+        options.check_depth(&type_, 0, 0, 0),
+        Err(DecodeError::SignatureError(TypeError::DictDepth(2)))
+    );
+}
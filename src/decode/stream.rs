@@ -0,0 +1,210 @@
+#[cfg(target_family = "unix")]
+use crate::decode::DecodeResult;
+#[cfg(target_family = "unix")]
+use crate::decode::Decoder;
+use crate::decode::{
+    DecodeError, DecodeOptions, DecodePathElement, LocatedDecodeError, LocatedDecodeResult,
+};
+use crate::message::Message;
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+#[cfg(target_family = "unix")]
+use bytes::Buf;
+use bytes::{Bytes, BytesMut};
+
+/// Locate an error, which occurred during the decoding of the header of the next [`Message`].
+fn header_error(error: DecodeError) -> LocatedDecodeError {
+    LocatedDecodeError::new(error, 0, vec![DecodePathElement::Header])
+}
+
+/// A stateful decoder, which decodes [`Message`]s from a stream of bytes.
+///
+/// The bytes and the received FDs can be added in arbitrary chunks. Every call of [`decode`]
+/// returns the next complete [`Message`], if there is one. The remaining bytes and the unconsumed
+/// FDs are kept for the next [`Message`].
+///
+/// # Example
+/// ```
+/// # use dbus_message_parser::decode::MessageStreamDecoder;
+/// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+/// let mut decoder = MessageStreamDecoder::new();
+///
+/// decoder.extend_from_slice(&msg[..20]);
+/// assert_eq!(decoder.decode(), Ok(None));
+///
+/// decoder.extend_from_slice(&msg[20..]);
+/// assert!(decoder.decode().unwrap().is_some());
+/// assert_eq!(decoder.decode(), Ok(None));
+/// ```
+///
+/// [`decode`]: MessageStreamDecoder::decode
+#[derive(Debug, Default)]
+pub struct MessageStreamDecoder {
+    buf: BytesMut,
+    #[cfg(target_family = "unix")]
+    fds: Vec<UnixFd>,
+    options: DecodeOptions,
+    poisoned: bool,
+}
+
+impl MessageStreamDecoder {
+    /// Create a [`MessageStreamDecoder`] object with an empty buffer.
+    pub fn new() -> MessageStreamDecoder {
+        MessageStreamDecoder::default()
+    }
+
+    /// Create a [`MessageStreamDecoder`] object with an empty buffer, which decodes the
+    /// [`Message`]s with the given limits.
+    pub fn with_options(options: DecodeOptions) -> MessageStreamDecoder {
+        MessageStreamDecoder {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Append received bytes to the buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Get the buffer, for example to read from a socket directly into it.
+    #[inline]
+    pub fn buffer_mut(&mut self) -> &mut BytesMut {
+        &mut self.buf
+    }
+
+    /// Append received FDs. The FDs are closed, when they are consumed by a decoded [`Message`],
+    /// which holds duplicates of the FDs, or when the [`Message`] is skipped.
+    #[cfg(target_family = "unix")]
+    pub fn extend_fds<I>(&mut self, fds: I)
+    where
+        I: IntoIterator<Item = UnixFd>,
+    {
+        self.fds.extend(fds);
+    }
+
+    /// Get the FDs, which are not consumed by a decoded [`Message`] yet.
+    #[cfg(target_family = "unix")]
+    #[inline]
+    pub fn get_fds(&self) -> &[UnixFd] {
+        &self.fds
+    }
+
+    /// Decode the next [`Message`] from the buffer.
+    ///
+    /// Returns `None` if the buffer does not contain a complete [`Message`] yet. The bytes and the
+    /// declared FDs of the [`Message`] are removed, even if the [`Message`] could not be decoded.
+    ///
+    /// The FDs of a [`Message`] are received together with its first byte. If the buffer contains
+    /// a complete [`Message`], but fewer FDs than declared in the [`unix_fds`] header field, a
+    /// [`DecodeError::NotEnoughFds`] is returned and the [`Message`] is removed together with the
+    /// received FDs.
+    ///
+    /// Messages with an unknown [`MessageType`] are skipped together with their FDs, as the
+    /// specification requires.
+    ///
+    /// If the length of the next [`Message`] cannot be determined, because the endianness is
+    /// invalid ([`DecodeError::Endianness`]) or the length overflows
+    /// ([`DecodeError::IntegerOverflow`]), or if the declared length exceeds the maximum message
+    /// length ([`DecodeError::MessageTooLong`]), the start of the next [`Message`] is unknown. In
+    /// this case the decoder is poisoned: the bytes are kept and every later call returns the same
+    /// error, without waiting for the rest of the [`Message`]. The stream cannot be recovered and
+    /// a new decoder has to be created for a new stream.
+    ///
+    /// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
+    /// [`MessageType`]: crate::message::MessageType
+    pub fn decode(&mut self) -> LocatedDecodeResult<Option<Message>> {
+        loop {
+            let length = match Message::decode_length(&self.buf) {
+                Ok(length) => length,
+                Err(e) => {
+                    if let DecodeError::NotEnoughBytes(_, _) = e.get_error() {
+                        if !self.poisoned {
+                            return Ok(None);
+                        }
+                    }
+                    return Err(self.poison(e));
+                }
+            };
+            if self.options.maximum_message_length < length {
+                let error = header_error(DecodeError::MessageTooLong(length));
+                return Err(self.poison(error));
+            }
+            if self.buf.len() < length {
+                return Ok(None);
+            }
+
+            // If the header cannot be decoded, then the error is returned by `decode_message`.
+            #[cfg(target_family = "unix")]
+            let unix_fds = message_unix_fds(&self.buf[..length]).ok();
+            #[cfg(target_family = "unix")]
+            if let Some(unix_fds) = unix_fds {
+                let fds_len = self.fds.len();
+                if fds_len < unix_fds {
+                    self.buf.advance(length);
+                    self.fds.clear();
+                    return Err(header_error(DecodeError::NotEnoughFds(fds_len, unix_fds)));
+                }
+            }
+
+            let bytes = self.buf.split_to(length).freeze();
+            let result = self.decode_message(bytes);
+            #[cfg(target_family = "unix")]
+            if let Some(unix_fds) = unix_fds {
+                self.skip_fds(unix_fds);
+            }
+            match result {
+                Ok(msg) => return Ok(Some(msg)),
+                Err(e) => {
+                    if let DecodeError::UnknownMessageType(_) = e.get_error() {
+                        continue;
+                    } else {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the length of a [`Message`] could not be determined. Every later call of
+    /// [`decode`] returns an error.
+    ///
+    /// [`decode`]: MessageStreamDecoder::decode
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Poison the decoder, because the start of the next [`Message`] is unknown.
+    fn poison(&mut self, error: LocatedDecodeError) -> LocatedDecodeError {
+        self.poisoned = true;
+        error
+    }
+
+    #[cfg(target_family = "unix")]
+    fn decode_message(&self, bytes: Bytes) -> LocatedDecodeResult<Message> {
+        let (msg, _, _) = Message::decode_with_fds_and_options(bytes, &self.fds, self.options)?;
+        Ok(msg)
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    fn decode_message(&self, bytes: Bytes) -> LocatedDecodeResult<Message> {
+        let (msg, _) = Message::decode_with_options(bytes, self.options)?;
+        Ok(msg)
+    }
+
+    /// Remove the FDs of a consumed message.
+    #[cfg(target_family = "unix")]
+    fn skip_fds(&mut self, unix_fds: usize) {
+        self.fds.drain(..unix_fds);
+    }
+}
+
+/// Decode the number of FDs, which is declared in the header fields of the message, without
+/// checking the message type.
+#[cfg(target_family = "unix")]
+fn message_unix_fds(bytes: &[u8]) -> DecodeResult<usize> {
+    let mut decoder = Decoder::new(bytes);
+    let unix_fds = decoder.message_unix_fds()?;
+    Ok(unix_fds as usize)
+}
@@ -0,0 +1,320 @@
+use crate::decode::{
+    DecodeError, DecodeOptions, DecodePathElement, DecodeResult, Decoder, LocatedDecodeError,
+    LocatedDecodeResult,
+};
+use crate::encode::algin;
+use crate::message::{Message, MAJOR_PROTOCOL_VERSION};
+use crate::value::Type;
+use std::convert::TryFrom;
+
+/// A helper struct to swap the endianness of an encoded message in place. It walks the header and
+/// the body according to the signature and reverses the bytes of all integers, lengths and
+/// doubles.
+struct Swapper<'a> {
+    buf: &'a mut [u8],
+    offset: usize,
+    is_le: bool,
+    options: DecodeOptions,
+    path: Vec<DecodePathElement>,
+}
+
+impl<'a> Swapper<'a> {
+    /// Add the current offset and path to a [`DecodeError`].
+    fn located(&self, error: DecodeError) -> LocatedDecodeError {
+        LocatedDecodeError::new(error, self.offset, self.path.clone())
+    }
+
+    /// Enter a container and check the total depth of the nested containers.
+    fn container_depth(&self, container_depth: u8) -> DecodeResult<u8> {
+        let container_depth = container_depth.saturating_add(1);
+        if self.options.maximum_container_depth < container_depth {
+            Err(DecodeError::ContainerDepth(container_depth))
+        } else {
+            Ok(container_depth)
+        }
+    }
+
+    /// Skip `length` bytes and returns the start offset.
+    fn read(&mut self, length: usize) -> DecodeResult<usize> {
+        let start = self.offset;
+        let end = Decoder::checked_add(start, length)?;
+        let buf_len = self.buf.len();
+        if end <= buf_len {
+            self.offset = end;
+            Ok(start)
+        } else {
+            Err(DecodeError::NotEnoughBytes(buf_len, end))
+        }
+    }
+
+    fn algin(&mut self, a: usize) -> DecodeResult<()> {
+        let remain = self.offset % a;
+        if remain != 0 {
+            let start = self.read(a - remain)?;
+            for b in &self.buf[start..self.offset] {
+                if *b != 0 {
+                    return Err(DecodeError::Padding(*b));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the alignment and reverse the next `a` bytes.
+    fn swap(&mut self, a: usize) -> DecodeResult<()> {
+        self.algin(a)?;
+        let start = self.read(a)?;
+        self.buf[start..self.offset].reverse();
+        Ok(())
+    }
+
+    fn u_8(&mut self) -> DecodeResult<u8> {
+        let start = self.read(1)?;
+        Ok(self.buf[start])
+    }
+
+    /// Apply the alignment, decode a `u32` in the original endianness and reverse its bytes.
+    fn u_32(&mut self) -> DecodeResult<u32> {
+        self.algin(4)?;
+        let start = self.read(4)?;
+        let bytes = &mut self.buf[start..self.offset];
+        let mut u = [0; 4];
+        u.copy_from_slice(bytes);
+        bytes.reverse();
+        if self.is_le {
+            Ok(u32::from_le_bytes(u))
+        } else {
+            Ok(u32::from_be_bytes(u))
+        }
+    }
+
+    /// Skip a string, which length is encoded as a `u8`, and returns the start and the end offset
+    /// of the string without the null byte.
+    fn u8_string(&mut self) -> DecodeResult<(usize, usize)> {
+        let length = self.u_8()? as usize;
+        let start = self.read(length + 1)?;
+        Ok((start, start + length))
+    }
+
+    /// Skip the signature of a variant and check the depth of the type.
+    fn signature_type(&mut self) -> DecodeResult<Type> {
+        let (start, end) = self.u8_string()?;
+        let type_ = Type::try_from(&self.buf[start..end])?;
+        self.options.check_depth(&type_, 0, 0, 0)?;
+        Ok(type_)
+    }
+
+    fn variant(&mut self, mut variant_depth: u8, container_depth: u8) -> DecodeResult<()> {
+        variant_depth += 1;
+        if self.options.maximum_variant_depth < variant_depth {
+            return Err(DecodeError::VariantDepth(variant_depth));
+        }
+
+        let type_ = self.signature_type()?;
+        self.path.push(DecodePathElement::Variant);
+        self.value(&type_, variant_depth, container_depth)?;
+        self.path.pop();
+        Ok(())
+    }
+
+    fn value(
+        &mut self,
+        type_: &Type,
+        variant_depth: u8,
+        mut container_depth: u8,
+    ) -> DecodeResult<()> {
+        if let Type::Variant | Type::Array(_) | Type::Struct(_) | Type::DictEntry(_) = type_ {
+            container_depth = self.container_depth(container_depth)?;
+        }
+
+        match type_ {
+            Type::Byte => {
+                self.read(1)?;
+                Ok(())
+            }
+            Type::Int16 | Type::Uint16 => self.swap(2),
+            Type::Boolean | Type::Int32 | Type::Uint32 => self.swap(4),
+            Type::UnixFD => self.swap(4),
+            Type::Int64 | Type::Uint64 | Type::Double => self.swap(8),
+            Type::String | Type::ObjectPath => {
+                let length = self.u_32()? as usize;
+                self.read(Decoder::checked_add(length, 1)?)?;
+                Ok(())
+            }
+            Type::Signature => {
+                self.u8_string()?;
+                Ok(())
+            }
+            Type::Variant => self.variant(variant_depth, container_depth),
+            Type::Array(type_) => {
+                let array_size = self.u_32()?;
+                if self.options.maximum_array_length < array_size as usize {
+                    return Err(DecodeError::ArrayTooBig(array_size));
+                }
+                self.algin(type_.get_alignment())?;
+                let end = Decoder::checked_add(self.offset, array_size as usize)?;
+                let mut i = 0;
+                while self.offset < end {
+                    self.path.push(DecodePathElement::ArrayElement(i));
+                    self.value(type_, variant_depth, container_depth)?;
+                    self.path.pop();
+                    i += 1;
+                }
+                if self.offset == end {
+                    Ok(())
+                } else {
+                    Err(DecodeError::ArrayInvalidLength(self.offset, end))
+                }
+            }
+            Type::Struct(types) => {
+                self.algin(8)?;
+                let path_element = DecodePathElement::StructField;
+                self.values(types, variant_depth, container_depth, path_element)
+            }
+            Type::DictEntry(types) => {
+                self.algin(8)?;
+                self.path.push(DecodePathElement::DictKey);
+                self.value(&types.0, variant_depth, container_depth)?;
+                self.path.pop();
+                self.path.push(DecodePathElement::DictValue);
+                self.value(&types.1, variant_depth, container_depth)?;
+                self.path.pop();
+                Ok(())
+            }
+            Type::Maybe(_) => Err(DecodeError::MaybeType),
+        }
+    }
+
+    fn values(
+        &mut self,
+        types: &[Type],
+        variant_depth: u8,
+        container_depth: u8,
+        path_element: fn(usize) -> DecodePathElement,
+    ) -> DecodeResult<()> {
+        for (i, type_) in types.iter().enumerate() {
+            self.path.push(path_element(i));
+            self.value(type_, variant_depth, container_depth)?;
+            self.path.pop();
+        }
+        Ok(())
+    }
+
+    /// Swap the header and returns the length and the signature of the body.
+    fn message_header(&mut self) -> DecodeResult<(u32, Vec<Type>)> {
+        let start = self.read(4)?;
+        let version = self.buf[start + 3];
+        if version != MAJOR_PROTOCOL_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        self.is_le = match self.buf[start] {
+            0x6c => {
+                self.buf[start] = 0x42;
+                true
+            }
+            0x42 => {
+                self.buf[start] = 0x6c;
+                false
+            }
+            b => return Err(DecodeError::Endianness(b)),
+        };
+
+        let body_length = self.u_32()?;
+        // The serial number.
+        self.u_32()?;
+
+        // The header fields are swapped one by one to get the signature of the body.
+        let array_size = self.u_32()? as usize;
+        self.algin(8)?;
+        let end = Decoder::checked_add(self.offset, array_size)?;
+        let length = Decoder::checked_add(algin(end, 8), body_length as usize)?;
+        if self.options.maximum_message_length < length {
+            return Err(DecodeError::MessageTooLong(length));
+        }
+        let mut body_signature = Vec::new();
+        while self.offset < end {
+            self.algin(8)?;
+            let number = self.u_8()?;
+            let type_ = self.signature_type()?;
+            if number == 8 && type_ == Type::Signature {
+                let (start, end) = self.u8_string()?;
+                body_signature = Type::from_bytes_to_signature(&self.buf[start..end])?;
+                for type_ in &body_signature {
+                    self.options.check_depth(type_, 0, 0, 0)?;
+                }
+            } else {
+                // The value is nested in the array, the struct and the variant of the header fields.
+                self.value(&type_, 1, 3)?;
+            }
+        }
+        if self.offset != end {
+            return Err(DecodeError::ArrayInvalidLength(self.offset, end));
+        }
+
+        Ok((body_length, body_signature))
+    }
+
+    fn message(&mut self) -> DecodeResult<()> {
+        self.path.push(DecodePathElement::Header);
+        let (body_length, body_signature) = self.message_header()?;
+        self.path.pop();
+
+        self.algin(8)?;
+
+        let end = Decoder::checked_add(self.offset, body_length as usize)?;
+        self.values(&body_signature, 0, 0, DecodePathElement::BodyArgument)?;
+        if self.offset == end {
+            Ok(())
+        } else {
+            Err(DecodeError::BodyLength(end, self.offset))
+        }
+    }
+}
+
+impl Message {
+    /// Swap the endianness of the encoded [`Message`] at the start of `bytes` in place, without
+    /// decoding it. Returns the length of the [`Message`].
+    ///
+    /// The header and the body are walked according to the signature of the body and the bytes of
+    /// all integers, lengths and doubles are reversed. The limits and the padding are checked in
+    /// the same way as in [`decode`], and the error contains the location, where the [`Message`]
+    /// could not be swapped. If an error occurs, `bytes` can be partially swapped. To keep the
+    /// original, swap a copy of it.
+    ///
+    /// # Example
+    /// ```
+    /// # use bytes::Bytes;
+    /// # use dbus_message_parser::message::Message;
+    /// let msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0";
+    /// let mut bytes = msg.to_vec();
+    ///
+    /// assert_eq!(Message::swap_endianness(&mut bytes), Ok(msg.len()));
+    /// let (msg_1, _) = Message::decode(Bytes::copy_from_slice(&msg[..])).unwrap();
+    /// let (msg_2, _) = Message::decode(Bytes::from(bytes)).unwrap();
+    /// assert!(msg_1.is_le());
+    /// assert!(!msg_2.is_le());
+    /// assert_eq!(msg_1.get_body(), msg_2.get_body());
+    /// ```
+    ///
+    /// [`decode`]: Message::decode
+    pub fn swap_endianness(bytes: &mut [u8]) -> LocatedDecodeResult<usize> {
+        Message::swap_endianness_with_options(bytes, DecodeOptions::default())
+    }
+
+    /// Swap the endianness of the encoded [`Message`] at the start of `bytes` in place with the
+    /// given limits. Returns the length of the [`Message`].
+    pub fn swap_endianness_with_options(
+        bytes: &mut [u8],
+        options: DecodeOptions,
+    ) -> LocatedDecodeResult<usize> {
+        let mut swapper = Swapper {
+            buf: bytes,
+            offset: 0,
+            is_le: true,
+            options,
+            path: Vec::new(),
+        };
+        swapper.message().map_err(|e| swapper.located(e))?;
+        Ok(swapper.offset)
+    }
+}
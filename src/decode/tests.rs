@@ -4,7 +4,7 @@ use bytes::Bytes;
 #[test]
 fn algin_padding_error() {
     let b = Bytes::from_static(b"\x01\x01");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     decoder.offset = 1;
     assert_eq!(decoder.algin(2), Err(DecodeError::Padding(0x01)));
 }
@@ -12,13 +12,31 @@ fn algin_padding_error() {
 #[test]
 fn algin_not_engough_bytes_error() {
     let b = Bytes::from_static(b"\x01");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     decoder.offset = 1;
     assert_eq!(decoder.algin(2), Err(DecodeError::NotEnoughBytes(1, 2)));
 }
 
 #[test]
+#[allow(clippy::legacy_numeric_constants)]
 fn check_add_integer_overflow() {
     let u = Decoder::<'static>::checked_add(std::usize::MAX, 1);
     assert_eq!(u, Err(DecodeError::IntegerOverflow(std::usize::MAX, 1)));
 }
+
+#[test]
+fn maybe_type_error() {
+    use crate::value::Type;
+
+    let b = Bytes::from_static(b"\x01\x00\x00\x00");
+    let mut decoder = Decoder::new(&b);
+    let type_ = Type::Maybe(Box::new(Type::Int32));
+    assert_eq!(
+        decoder.value(true, 0, 0, &type_),
+        Err(DecodeError::MaybeType)
+    );
+    assert_eq!(
+        decoder.value_ref(true, 0, 0, &type_),
+        Err(DecodeError::MaybeType)
+    );
+}
@@ -1,8 +1,8 @@
 use crate::decode::{DecodeError, DecodeResult, Decoder};
-use crate::value::{ObjectPath, Type, Value};
-use bytes::{Buf, Bytes};
 #[cfg(target_family = "unix")]
-use std::cmp::max;
+use crate::value::UnixFd;
+use crate::value::{ObjectPath, Type, Value, ValueRef};
+use bytes::Buf;
 use std::convert::TryFrom;
 use std::mem::size_of;
 use std::str::from_utf8;
@@ -22,22 +22,25 @@ impl<'a> Decoder<'a> {
         Ok(Value::Byte(b))
     }
 
+    /// Check alignment and decode from a byte array at a specific offset to a `bool`.
+    pub(crate) fn d_boolean(&mut self, is_le: bool) -> DecodeResult<bool> {
+        match self.u_32(is_le)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            x => Err(DecodeError::InvalidBoolean(x)),
+        }
+    }
+
     /// Check alignment and decode from a byte array at a specific offset to a [`Value::Boolean`].
     ///
     /// [`Value::Boolean`]: crate::value::Value::Boolean
     pub(crate) fn boolean(&mut self, is_le: bool) -> DecodeResult<Value> {
-        let r = self.u_32(is_le)?;
-        match r {
-            0 => Ok(Value::Boolean(false)),
-            1 => Ok(Value::Boolean(true)),
-            x => Err(DecodeError::InvalidBoolean(x)),
-        }
+        let b = self.d_boolean(is_le)?;
+        Ok(Value::Boolean(b))
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Int16`].
-    ///
-    /// [`Value::Int16`]: crate::value::Value::Int16
-    pub(crate) fn int_16(&mut self, is_le: bool) -> DecodeResult<Value> {
+    /// Check alignment and decode from a byte array at a specific offset to a `i16`.
+    pub(crate) fn i_16(&mut self, is_le: bool) -> DecodeResult<i16> {
         self.algin(2)?;
         let mut buf = self.read(size_of::<i16>())?;
         let i = if is_le {
@@ -45,27 +48,39 @@ impl<'a> Decoder<'a> {
         } else {
             buf.get_i16()
         };
-        Ok(Value::Int16(i))
+        Ok(i)
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Uint16`].
+    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Int16`].
     ///
-    /// [`Value::Uint16`]: crate::value::Value::Uint16
-    pub(crate) fn uint_16(&mut self, is_le: bool) -> DecodeResult<Value> {
+    /// [`Value::Int16`]: crate::value::Value::Int16
+    pub(crate) fn int_16(&mut self, is_le: bool) -> DecodeResult<Value> {
+        let i = self.i_16(is_le)?;
+        Ok(Value::Int16(i))
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a `u16`.
+    pub(crate) fn u_16(&mut self, is_le: bool) -> DecodeResult<u16> {
         self.algin(2)?;
         let mut buf = self.read(size_of::<u16>())?;
         let u = if is_le {
-            Value::Uint16(buf.get_u16_le())
+            buf.get_u16_le()
         } else {
-            Value::Uint16(buf.get_u16())
+            buf.get_u16()
         };
         Ok(u)
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Int32`].
+    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Uint16`].
     ///
-    /// [`Value::Int32`]: crate::value::Value::Uint32
-    pub(crate) fn int_32(&mut self, is_le: bool) -> DecodeResult<Value> {
+    /// [`Value::Uint16`]: crate::value::Value::Uint16
+    pub(crate) fn uint_16(&mut self, is_le: bool) -> DecodeResult<Value> {
+        let u = self.u_16(is_le)?;
+        Ok(Value::Uint16(u))
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a `i32`.
+    pub(crate) fn i_32(&mut self, is_le: bool) -> DecodeResult<i32> {
         self.algin(4)?;
         let mut buf = self.read(size_of::<i32>())?;
         let i = if is_le {
@@ -73,6 +88,14 @@ impl<'a> Decoder<'a> {
         } else {
             buf.get_i32()
         };
+        Ok(i)
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Int32`].
+    ///
+    /// [`Value::Int32`]: crate::value::Value::Uint32
+    pub(crate) fn int_32(&mut self, is_le: bool) -> DecodeResult<Value> {
+        let i = self.i_32(is_le)?;
         Ok(Value::Int32(i))
     }
 
@@ -96,24 +119,70 @@ impl<'a> Decoder<'a> {
         Ok(Value::Uint32(u))
     }
 
+    /// Check alignment and decode from a byte array at a specific offset to an index of a FD. The
+    /// index has to be smaller than the number of FDs, which is declared in the `UNIX_FDS` header
+    /// field.
+    pub(crate) fn d_unix_fd_index(&mut self, is_le: bool) -> DecodeResult<u32> {
+        let i = self.u_32(is_le)?;
+        if let Some(unix_fds) = self.unix_fds {
+            if unix_fds <= i {
+                return Err(DecodeError::UnixFdIndex(i, unix_fds));
+            }
+        }
+        Ok(i)
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a [`UnixFd`], which
+    /// references the FDs.
+    ///
+    /// [`UnixFd`]: crate::value::UnixFd
+    #[cfg(target_family = "unix")]
+    fn d_unix_fd(&mut self, is_le: bool, fds: &'a [UnixFd]) -> DecodeResult<&'a UnixFd> {
+        let i = self.d_unix_fd_index(is_le)? as usize;
+        if let Some(fd) = fds.get(i) {
+            Ok(fd)
+        } else {
+            Err(DecodeError::NotEnoughFds(fds.len(), i))
+        }
+    }
+
     /// Check alignment and decode from a byte array at a specific offset to a [`Value::UnixFD`].
+    /// The FD is duplicated. If the decoder has no FDs, then it is decoded to a
+    /// [`Value::UnixFdIndex`].
     ///
     /// [`Value::UnixFD`]: crate::value::Value::UnixFD
-    #[cfg(target_family = "unix")]
+    /// [`Value::UnixFdIndex`]: crate::value::Value::UnixFdIndex
     pub(crate) fn unix_fd(&mut self, is_le: bool) -> DecodeResult<Value> {
-        let i = self.u_32(is_le)? as usize;
-        if let Some(fd) = self.fds.get(i) {
-            self.offset_fds = max(Some(i), self.offset_fds);
-            Ok(Value::UnixFD(*fd))
-        } else {
-            Err(DecodeError::NotEnoughFds(self.fds.len(), i))
+        #[cfg(target_family = "unix")]
+        if let Some(fds) = self.fds {
+            let fd = self
+                .d_unix_fd(is_le, fds)?
+                .try_clone()
+                .map_err(|e| DecodeError::DuplicateFd(e.kind()))?;
+            return Ok(Value::UnixFD(fd));
         }
+        let i = self.d_unix_fd_index(is_le)?;
+        Ok(Value::UnixFdIndex(i))
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Int64`].
+    /// Check alignment and decode from a byte array at a specific offset to a
+    /// [`ValueRef::UnixFD`], which borrows the FD. If the decoder has no FDs, then it is decoded
+    /// to a [`ValueRef::UnixFdIndex`].
     ///
-    /// [`Value::Int64`]: crate::value::Value::Int64
-    pub(crate) fn int_64(&mut self, is_le: bool) -> DecodeResult<Value> {
+    /// [`ValueRef::UnixFD`]: crate::value::ValueRef::UnixFD
+    /// [`ValueRef::UnixFdIndex`]: crate::value::ValueRef::UnixFdIndex
+    pub(crate) fn unix_fd_ref(&mut self, is_le: bool) -> DecodeResult<ValueRef<'a>> {
+        #[cfg(target_family = "unix")]
+        if let Some(fds) = self.fds {
+            let fd = self.d_unix_fd(is_le, fds)?;
+            return Ok(ValueRef::UnixFD(fd));
+        }
+        let i = self.d_unix_fd_index(is_le)?;
+        Ok(ValueRef::UnixFdIndex(i))
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a `i64`.
+    pub(crate) fn i_64(&mut self, is_le: bool) -> DecodeResult<i64> {
         self.algin(8)?;
         let mut buf = self.read(size_of::<i64>())?;
         let i = if is_le {
@@ -121,27 +190,39 @@ impl<'a> Decoder<'a> {
         } else {
             buf.get_i64()
         };
-        Ok(Value::Int64(i))
+        Ok(i)
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Uint64`].
+    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Int64`].
     ///
-    /// [`Value::Uint64`]: crate::value::Value::Uint64
-    pub(crate) fn uint_64(&mut self, is_le: bool) -> DecodeResult<Value> {
+    /// [`Value::Int64`]: crate::value::Value::Int64
+    pub(crate) fn int_64(&mut self, is_le: bool) -> DecodeResult<Value> {
+        let i = self.i_64(is_le)?;
+        Ok(Value::Int64(i))
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a `u64`.
+    pub(crate) fn u_64(&mut self, is_le: bool) -> DecodeResult<u64> {
         self.algin(8)?;
         let mut buf = self.read(size_of::<u64>())?;
         let u = if is_le {
-            Value::Uint64(buf.get_u64_le())
+            buf.get_u64_le()
         } else {
-            Value::Uint64(buf.get_u64())
+            buf.get_u64()
         };
         Ok(u)
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Double`].
+    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Uint64`].
     ///
-    /// [`Value::Double`]: crate::value::Value::Double
-    pub(crate) fn double(&mut self, is_le: bool) -> DecodeResult<Value> {
+    /// [`Value::Uint64`]: crate::value::Value::Uint64
+    pub(crate) fn uint_64(&mut self, is_le: bool) -> DecodeResult<Value> {
+        let u = self.u_64(is_le)?;
+        Ok(Value::Uint64(u))
+    }
+
+    /// Check alignment and decode from a byte array at a specific offset to a `f64`.
+    pub(crate) fn f_64(&mut self, is_le: bool) -> DecodeResult<f64> {
         self.algin(8)?;
         let mut buf = self.read(size_of::<f64>())?;
         let f = if is_le {
@@ -149,56 +230,78 @@ impl<'a> Decoder<'a> {
         } else {
             buf.get_f64()
         };
-        Ok(Value::Double(f))
+        Ok(f)
     }
 
-    /// Returns n-`length` [`Bytes`] object and check if the next byte is null, because all string have
-    /// to be null terminated.
+    /// Check alignment and decode from a byte array at a specific offset to a [`Value::Double`].
     ///
-    /// [`Bytes`]: bytes::Bytes
+    /// [`Value::Double`]: crate::value::Value::Double
+    pub(crate) fn double(&mut self, is_le: bool) -> DecodeResult<Value> {
+        let f = self.f_64(is_le)?;
+        Ok(Value::Double(f))
+    }
+
+    /// Returns n-`length` bytes and check if the next byte is null, because all string have to be
+    /// null terminated. The string itself must not contain a null byte.
     #[inline]
-    fn d_string_bytes(&mut self, length: usize) -> DecodeResult<Bytes> {
+    fn d_string_bytes(&mut self, length: usize) -> DecodeResult<&'a [u8]> {
         let bytes = self.read(length)?;
+        if let Some(i) = bytes.iter().position(|b| *b == 0) {
+            return Err(DecodeError::StringNul(i));
+        }
         match self.u_8()? {
             0 => Ok(bytes),
             b => Err(DecodeError::StringNotNull(b)),
         }
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`String`].
+    /// Check alignment and decode from a byte array at a specific offset to a byte slice of a
+    /// string.
     /// The size of the length is 4.
-    ///
-    /// [`String`]: std::string:String
-    fn d_u32_string_bytes(&mut self, is_le: bool) -> DecodeResult<Bytes> {
+    fn d_u32_string_bytes(&mut self, is_le: bool) -> DecodeResult<&'a [u8]> {
         let string_length = self.u_32(is_le)? as usize;
         self.d_string_bytes(string_length)
     }
 
-    /// Check alignment and decode from a byte array at a specific offset to a [`String`].
-    /// The size of the length is 4.
-    ///
-    /// [`String`]: std::string:String
-    fn d_u8_string_bytes(&mut self) -> DecodeResult<Bytes> {
+    /// Decode from a byte array at a specific offset to a byte slice of a string.
+    /// The size of the length is 1.
+    fn d_u8_string_bytes(&mut self) -> DecodeResult<&'a [u8]> {
         let string_length = self.u_8()? as usize;
         self.d_string_bytes(string_length)
     }
 
+    /// Check alignment and decode from a byte array at a specific offset to a `&str`, which
+    /// references the byte array.
+    pub(crate) fn d_str(&mut self, is_le: bool) -> DecodeResult<&'a str> {
+        let bytes = self.d_u32_string_bytes(is_le)?;
+        let string = from_utf8(bytes)?;
+        Ok(string)
+    }
+
     /// Check alignment and decode from a byte array at a specific offset to a [`Value::String`].
     ///
     /// [`Value::String`]: crate::value::Value::String
     pub(crate) fn string(&mut self, is_le: bool) -> DecodeResult<Value> {
-        let bytes = self.d_u32_string_bytes(is_le)?;
-        let string = from_utf8(bytes.as_ref())?;
+        let string = self.d_str(is_le)?;
         Ok(Value::String(string.to_owned()))
     }
 
+    /// Check alignment and decode from a byte array at a specific offset to a `&str`, which
+    /// references the byte array and is a valid object path.
+    pub(crate) fn d_object_path_str(&mut self, is_le: bool) -> DecodeResult<&'a str> {
+        let bytes = self.d_u32_string_bytes(is_le)?;
+        ObjectPath::check(bytes)?;
+        let object_path = from_utf8(bytes)?;
+        Ok(object_path)
+    }
+
     /// Check alignment and decode from a byte array at a specific offset to a
     /// [`Value::ObjectPath`].
     ///
     /// [`Value::ObjectPath`]: crate::value::Value::ObjectPath
     pub(crate) fn object_path(&mut self, is_le: bool) -> DecodeResult<Value> {
         let bytes = self.d_u32_string_bytes(is_le)?;
-        let object_path = ObjectPath::try_from(bytes.as_ref())?;
+        let object_path = ObjectPath::try_from(bytes)?;
         Ok(Value::ObjectPath(object_path))
     }
 
@@ -208,7 +311,11 @@ impl<'a> Decoder<'a> {
     /// [`Type`]: crate::value::Type
     pub(crate) fn d_type(&mut self) -> DecodeResult<Type> {
         let bytes = self.d_u8_string_bytes()?;
-        let type_ = Type::try_from(bytes.as_ref())?;
+        let type_ = Type::try_from(bytes)?;
+        if type_.contains_maybe() {
+            return Err(DecodeError::MaybeType);
+        }
+        self.options.check_depth(&type_, 0, 0, 0)?;
         Ok(type_)
     }
 
@@ -218,7 +325,13 @@ impl<'a> Decoder<'a> {
     /// [`Signature`]: crate::value::Signature
     pub(crate) fn d_signature(&mut self) -> DecodeResult<Vec<Type>> {
         let bytes = self.d_u8_string_bytes()?;
-        let signature = Type::from_bytes_to_signature(bytes.as_ref())?;
+        let signature = Type::from_bytes_to_signature(bytes)?;
+        for type_ in &signature {
+            if type_.contains_maybe() {
+                return Err(DecodeError::MaybeType);
+            }
+            self.options.check_depth(type_, 0, 0, 0)?;
+        }
         Ok(signature)
     }
     /// Decode from a byte array at a specific offset to a [`Value::Signature`].
@@ -1,19 +1,27 @@
-use crate::decode::{DecodeError, DecodeResult, Decoder, MAXIMUM_VARIANT_DEPTH};
-use crate::value::{Array, Struct, Type, Value, MAXIMUM_ARRAY_LENGTH};
+use crate::decode::{DecodeError, DecodePathElement, DecodeResult, Decoder};
+use crate::value::{Array, Struct, Type, Value};
 use std::convert::TryFrom;
 
 impl<'a> Decoder<'a> {
     /// Decode from a byte array at a specific offset to a [`Value::Variant`].
     ///
     /// [`Value::Variant`]: crate::value::Value::Variant
-    pub fn variant(&mut self, is_le: bool, mut variant_depth: u8) -> DecodeResult<Value> {
+    pub fn variant(
+        &mut self,
+        is_le: bool,
+        mut variant_depth: u8,
+        container_depth: u8,
+    ) -> DecodeResult<Value> {
+        let container_depth = self.container_depth(container_depth)?;
         variant_depth += 1;
-        if MAXIMUM_VARIANT_DEPTH < variant_depth {
+        if self.options.maximum_variant_depth < variant_depth {
             return Err(DecodeError::VariantDepth(variant_depth));
         }
 
         let type_ = self.d_type()?;
-        let value = self.value(is_le, variant_depth, &type_)?;
+        self.path.push(DecodePathElement::Variant);
+        let value = self.value(is_le, variant_depth, container_depth, &type_)?;
+        self.path.pop();
         Ok(Value::Variant(Box::new(value)))
     }
 
@@ -22,20 +30,27 @@ impl<'a> Decoder<'a> {
         &mut self,
         is_le: bool,
         variant_depth: u8,
+        container_depth: u8,
         type_: &Type,
     ) -> DecodeResult<Vec<Value>> {
+        let container_depth = self.container_depth(container_depth)?;
         let array_size = self.u_32(is_le)?;
-        if MAXIMUM_ARRAY_LENGTH < array_size as usize {
+        if self.options.maximum_array_length < array_size as usize {
             return Err(DecodeError::ArrayTooBig(array_size));
         }
 
         self.algin(type_.get_alignment())?;
         let mut array = Vec::new();
         let end = Decoder::<'a>::checked_add(self.offset, array_size as usize)?;
+        self.path.push(DecodePathElement::ArrayElement(0));
         while self.offset < end {
-            let value = self.value(is_le, variant_depth, type_)?;
+            if let Some(path_element) = self.path.last_mut() {
+                *path_element = DecodePathElement::ArrayElement(array.len());
+            }
+            let value = self.value(is_le, variant_depth, container_depth, type_)?;
             array.push(value);
         }
+        self.path.pop();
 
         if self.offset == end {
             Ok(array)
@@ -47,8 +62,14 @@ impl<'a> Decoder<'a> {
     /// Decode from a byte array at a specific offset to a [`Value::Array`].
     ///
     /// [`Value::Array`]: crate::value::Value::Array
-    pub fn array(&mut self, is_le: bool, variant_depth: u8, type_: &Type) -> DecodeResult<Value> {
-        let array = self.d_array(is_le, variant_depth, type_)?;
+    pub fn array(
+        &mut self,
+        is_le: bool,
+        variant_depth: u8,
+        container_depth: u8,
+        type_: &Type,
+    ) -> DecodeResult<Value> {
+        let array = self.d_array(is_le, variant_depth, container_depth, type_)?;
         let array = Array {
             array,
             type_: type_.clone(),
@@ -63,10 +84,18 @@ impl<'a> Decoder<'a> {
         &mut self,
         is_le: bool,
         variant_depth: u8,
+        container_depth: u8,
         signature: &[Type],
     ) -> DecodeResult<Value> {
+        let container_depth = self.container_depth(container_depth)?;
         self.algin(8)?;
-        let values = self.values(is_le, variant_depth, signature)?;
+        let values = self.values(
+            is_le,
+            variant_depth,
+            container_depth,
+            signature,
+            DecodePathElement::StructField,
+        )?;
         let struct_ = Struct::try_from(values)?;
         Ok(Value::Struct(struct_))
     }
@@ -78,12 +107,18 @@ impl<'a> Decoder<'a> {
         &mut self,
         is_le: bool,
         variant_depth: u8,
+        container_depth: u8,
         key_type: &Type,
         value_type: &Type,
     ) -> DecodeResult<Value> {
+        let container_depth = self.container_depth(container_depth)?;
         self.algin(8)?;
-        let key = self.value(is_le, variant_depth, key_type)?;
-        let value = self.value(is_le, variant_depth, value_type)?;
+        self.path.push(DecodePathElement::DictKey);
+        let key = self.value(is_le, variant_depth, container_depth, key_type)?;
+        self.path.pop();
+        self.path.push(DecodePathElement::DictValue);
+        let value = self.value(is_le, variant_depth, container_depth, value_type)?;
+        self.path.pop();
         Ok(Value::DictEntry(Box::new((key, value))))
     }
 }
@@ -3,3 +3,4 @@ mod container;
 #[cfg(test)]
 mod tests;
 mod value_enum;
+mod value_ref;
@@ -1,4 +1,6 @@
-use crate::decode::{DecodeError, Decoder};
+use crate::decode::{DecodeError, DecodePathElement, Decoder};
+#[cfg(target_family = "unix")]
+use crate::value::open_null;
 use crate::value::{Type, Value};
 use bytes::Bytes;
 use std::convert::TryInto;
@@ -7,17 +9,21 @@ macro_rules! init_test {
     ($array:tt, $le:expr, $sig:expr) => {{
         let sig = Type::from_string_to_signature($sig).unwrap();
         let b = Bytes::from_static(&$array[..]);
-        let mut decoder = Decoder::new(b);
-        let mut v = decoder.values($le, 0, &sig).unwrap();
+        let mut decoder = Decoder::new(&b);
+        let mut v = decoder
+            .values($le, 0, 0, &sig, DecodePathElement::BodyArgument)
+            .unwrap();
         assert_eq!(v.len(), 1);
         v.pop().unwrap()
     }};
     ($array:tt, $offset:expr, $le:expr, $sig:expr) => {{
         let sig = Type::from_string_to_signature($sig).unwrap();
         let b = Bytes::from_static(&$array[..]);
-        let mut decoder = Decoder::new(b);
+        let mut decoder = Decoder::new(&b);
         decoder.offset = $offset;
-        let mut v = decoder.values($le, 0, &sig).unwrap();
+        let mut v = decoder
+            .values($le, 0, 0, &sig, DecodePathElement::BodyArgument)
+            .unwrap();
         assert_eq!(v.len(), 1);
         v.pop().unwrap()
     }};
@@ -27,8 +33,8 @@ macro_rules! init_error_test {
     ($array:tt, $sig:expr) => {{
         let sig = Type::from_string_to_signature($sig).unwrap();
         let b = Bytes::from_static(&$array[..]);
-        let mut decoder = Decoder::new(b);
-        decoder.values(true, 0, &sig)
+        let mut decoder = Decoder::new(&b);
+        decoder.values(true, 0, 0, &sig, DecodePathElement::BodyArgument)
     }};
 }
 
@@ -126,23 +132,34 @@ fn uint_16_3() {
 #[test]
 fn unix_fd() {
     let b = Bytes::from_static(&b"\x00\x00\x00\x00"[..]);
-    let fds = [2];
+    let fds = [open_null()];
     let type_ = Type::UnixFD;
-    let mut decoder = Decoder::new_with_fds(b, &fds[..]);
-    let mut v = decoder.values(true, 0, &[type_]).unwrap();
+    let mut decoder = Decoder::new_with_fds(&b, &fds[..]);
+    let mut v = decoder
+        .values(true, 0, 0, &[type_], DecodePathElement::BodyArgument)
+        .unwrap();
     assert_eq!(v.len(), 1);
-    let v = v.pop().unwrap();
-    assert_eq!(v, Value::UnixFD(2));
+    // The FD is duplicated.
+    match v.pop().unwrap() {
+        Value::UnixFD(fd) => assert_ne!(fd, fds[0]),
+        v => panic!("Value is not a FD: {:?}", v),
+    }
+}
+
+#[test]
+fn unix_fd_index() {
+    let v = init_test!(b"\x02\x00\x00\x00", true, "h");
+    assert_eq!(v, Value::UnixFdIndex(2));
 }
 
 #[cfg(target_family = "unix")]
 #[test]
 fn unix_fd_error() {
     let b = Bytes::from_static(&b"\x01\x00\x00\x00"[..]);
-    let fds = [2];
+    let fds = [open_null()];
     let type_ = Type::UnixFD;
-    let mut decoder = Decoder::new_with_fds(b, &fds[..]);
-    let v = decoder.values(true, 0, &[type_]);
+    let mut decoder = Decoder::new_with_fds(&b, &fds[..]);
+    let v = decoder.values(true, 0, 0, &[type_], DecodePathElement::BodyArgument);
     assert_eq!(v, Err(DecodeError::NotEnoughFds(1, 1)));
 }
 
@@ -229,6 +246,12 @@ fn int_64_error() {
     assert_eq!(v, Err(DecodeError::NotEnoughBytes(7, 8)));
 }
 
+#[test]
+fn string_error_4() {
+    let v = init_error_test!(b"\x03\x00\x00\x00\x66\x00\x6f\x00", "s");
+    assert_eq!(v, Err(DecodeError::StringNul(1)));
+}
+
 #[test]
 fn uint_64_1() {
     let v = init_test!(b"\x01\x01\x01\x01\x01\x01\x01\xf0", true, "t");
@@ -375,3 +398,15 @@ fn signature_error_3() {
     let v = init_error_test!(b"\x01\x69", "g");
     assert_eq!(v, Err(DecodeError::NotEnoughBytes(2, 3)));
 }
+
+#[test]
+fn signature_error_4() {
+    let v = init_error_test!(b"\x02\x00\x69\x00", "g");
+    assert_eq!(v, Err(DecodeError::StringNul(0)));
+}
+
+#[test]
+fn signature_error_5() {
+    let v = init_error_test!(b"\x02\x6d\x69\x00", "g");
+    assert_eq!(v, Err(DecodeError::MaybeType));
+}
@@ -7,27 +7,37 @@ use bytes::Bytes;
 #[test]
 fn variant_depth_error() {
     let b = Bytes::from_static(b"\x01\x76\x00\x01\x76\x00\x01\x76\x00\x01\x76\x00");
-    let mut decoder = Decoder::new(b);
-    assert_eq!(decoder.variant(true, 0), Err(DecodeError::VariantDepth(5)));
+    let mut decoder = Decoder::new(&b);
+    assert_eq!(
+        decoder.variant(true, 0, 0),
+        Err(DecodeError::VariantDepth(5))
+    );
 }
 
 #[test]
 fn variant_single_value_error() {
     let b = Bytes::from_static(b"\x02\x79\x79\x00\x01\x01");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     assert_eq!(
-        decoder.variant(true, 0),
+        decoder.variant(true, 0, 0),
         Err(DecodeError::SignatureError(TypeError::MultiplyTypes))
     );
 }
 
+#[test]
+fn variant_maybe_type_error() {
+    let b = Bytes::from_static(b"\x02\x6d\x69\x00\x01\x00\x00\x00");
+    let mut decoder = Decoder::new(&b);
+    assert_eq!(decoder.variant(true, 0, 0), Err(DecodeError::MaybeType));
+}
+
 #[test]
 fn array_maximum_length_error() {
     let b = Bytes::from_static(b"\xff\xff\xff\xff");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     let type_ = Type::Byte;
     assert_eq!(
-        decoder.d_array(true, 0, &type_),
+        decoder.d_array(true, 0, 0, &type_),
         Err(DecodeError::ArrayTooBig(0xffffffff))
     );
 }
@@ -35,10 +45,21 @@ fn array_maximum_length_error() {
 #[test]
 fn array_invalid_length_error() {
     let b = Bytes::from_static(b"\x01\x00\x00\x00\x01\x00\x00\x00");
-    let mut decoder = Decoder::new(b);
+    let mut decoder = Decoder::new(&b);
     let type_ = Type::Int32;
     assert_eq!(
-        decoder.d_array(true, 0, &type_),
+        decoder.d_array(true, 0, 0, &type_),
         Err(DecodeError::ArrayInvalidLength(8, 5))
     );
 }
+
+#[test]
+fn container_depth_error() {
+    let b = Bytes::from_static(b"\x00\x00\x00\x00");
+    let mut decoder = Decoder::new(&b);
+    let type_ = Type::Byte;
+    assert_eq!(
+        decoder.d_array(true, 0, 64, &type_),
+        Err(DecodeError::ContainerDepth(65))
+    );
+}
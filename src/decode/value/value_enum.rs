@@ -1,18 +1,23 @@
-use crate::decode::{DecodeResult, Decoder};
+use crate::decode::{DecodeError, DecodePathElement, DecodeResult, Decoder};
 use crate::value::{Type, Value};
 
 impl<'a> Decoder<'a> {
-    /// Decode a byte array to a `Vec<Value>` object.
+    /// Decode a byte array to a `Vec<Value>` object. The path of each value is given by
+    /// `path_element`.
     pub(crate) fn values(
         &mut self,
         is_le: bool,
         variant_depth: u8,
+        container_depth: u8,
         signature: &[Type],
+        path_element: fn(usize) -> DecodePathElement,
     ) -> DecodeResult<Vec<Value>> {
         let mut result = Vec::with_capacity(signature.len());
         // Decode the value according to the signature.
-        for type_ in signature {
-            let value = self.value(is_le, variant_depth, type_)?;
+        for (i, type_) in signature.iter().enumerate() {
+            self.path.push(path_element(i));
+            let value = self.value(is_le, variant_depth, container_depth, type_)?;
+            self.path.pop();
             result.push(value);
         }
 
@@ -24,6 +29,7 @@ impl<'a> Decoder<'a> {
         &mut self,
         is_le: bool,
         variant_depth: u8,
+        container_depth: u8,
         type_: &Type,
     ) -> DecodeResult<Value> {
         match type_ {
@@ -39,14 +45,20 @@ impl<'a> Decoder<'a> {
             Type::String => self.string(is_le),
             Type::ObjectPath => self.object_path(is_le),
             Type::Signature => self.signature(),
-            #[cfg(target_family = "unix")]
             Type::UnixFD => self.unix_fd(is_le),
-            Type::Array(type_) => self.array(is_le, variant_depth, type_),
-            Type::Struct(signature) => self.decode_struct(is_le, variant_depth, signature),
-            Type::DictEntry(signature) => {
-                self.dict_entry(is_le, variant_depth, &signature.0, &signature.1)
+            Type::Array(type_) => self.array(is_le, variant_depth, container_depth, type_),
+            Type::Struct(signature) => {
+                self.decode_struct(is_le, variant_depth, container_depth, signature)
             }
-            Type::Variant => self.variant(is_le, variant_depth),
+            Type::DictEntry(signature) => self.dict_entry(
+                is_le,
+                variant_depth,
+                container_depth,
+                &signature.0,
+                &signature.1,
+            ),
+            Type::Variant => self.variant(is_le, variant_depth, container_depth),
+            Type::Maybe(_) => Err(DecodeError::MaybeType),
         }
     }
 }
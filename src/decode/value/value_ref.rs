@@ -0,0 +1,178 @@
+use crate::decode::{DecodeError, DecodePathElement, DecodeResult, Decoder};
+use crate::value::{ArrayRef, Type, ValueRef};
+
+impl<'a> Decoder<'a> {
+    /// Decode a byte array to a `Vec<ValueRef>` object. The path of each value is given by
+    /// `path_element`.
+    pub(crate) fn values_ref(
+        &mut self,
+        is_le: bool,
+        variant_depth: u8,
+        container_depth: u8,
+        signature: &[Type],
+        path_element: fn(usize) -> DecodePathElement,
+    ) -> DecodeResult<Vec<ValueRef<'a>>> {
+        let mut result = Vec::with_capacity(signature.len());
+        // Decode the value according to the signature.
+        for (i, type_) in signature.iter().enumerate() {
+            self.path.push(path_element(i));
+            let value = self.value_ref(is_le, variant_depth, container_depth, type_)?;
+            self.path.pop();
+            result.push(value);
+        }
+
+        Ok(result)
+    }
+
+    /// Decode a byte array to a [`ValueRef`] object, which references the byte array.
+    ///
+    /// [`ValueRef`]: crate::value::ValueRef
+    pub(crate) fn value_ref(
+        &mut self,
+        is_le: bool,
+        variant_depth: u8,
+        container_depth: u8,
+        type_: &Type,
+    ) -> DecodeResult<ValueRef<'a>> {
+        match type_ {
+            Type::Byte => Ok(ValueRef::Byte(self.u_8()?)),
+            Type::Boolean => Ok(ValueRef::Boolean(self.d_boolean(is_le)?)),
+            Type::Int16 => Ok(ValueRef::Int16(self.i_16(is_le)?)),
+            Type::Uint16 => Ok(ValueRef::Uint16(self.u_16(is_le)?)),
+            Type::Int32 => Ok(ValueRef::Int32(self.i_32(is_le)?)),
+            Type::Uint32 => Ok(ValueRef::Uint32(self.u_32(is_le)?)),
+            Type::Int64 => Ok(ValueRef::Int64(self.i_64(is_le)?)),
+            Type::Uint64 => Ok(ValueRef::Uint64(self.u_64(is_le)?)),
+            Type::Double => Ok(ValueRef::Double(self.f_64(is_le)?)),
+            Type::String => Ok(ValueRef::String(self.d_str(is_le)?)),
+            Type::ObjectPath => Ok(ValueRef::ObjectPath(self.d_object_path_str(is_le)?)),
+            Type::Signature => Ok(ValueRef::Signature(self.d_signature()?)),
+            Type::UnixFD => self.unix_fd_ref(is_le),
+            Type::Array(type_) => self.array_ref(is_le, variant_depth, container_depth, type_),
+            Type::Struct(signature) => {
+                self.struct_ref(is_le, variant_depth, container_depth, signature)
+            }
+            Type::DictEntry(signature) => self.dict_entry_ref(
+                is_le,
+                variant_depth,
+                container_depth,
+                &signature.0,
+                &signature.1,
+            ),
+            Type::Variant => self.variant_ref(is_le, variant_depth, container_depth),
+            Type::Maybe(_) => Err(DecodeError::MaybeType),
+        }
+    }
+
+    /// Decode from a byte array at a specific offset to a [`ValueRef::Variant`].
+    ///
+    /// [`ValueRef::Variant`]: crate::value::ValueRef::Variant
+    fn variant_ref(
+        &mut self,
+        is_le: bool,
+        mut variant_depth: u8,
+        container_depth: u8,
+    ) -> DecodeResult<ValueRef<'a>> {
+        let container_depth = self.container_depth(container_depth)?;
+        variant_depth += 1;
+        if self.options.maximum_variant_depth < variant_depth {
+            return Err(DecodeError::VariantDepth(variant_depth));
+        }
+
+        let type_ = self.d_type()?;
+        self.path.push(DecodePathElement::Variant);
+        let value = self.value_ref(is_le, variant_depth, container_depth, &type_)?;
+        self.path.pop();
+        Ok(ValueRef::Variant(Box::new(value)))
+    }
+
+    /// Decode from a byte array at a specific offset to a [`ValueRef::Array`].
+    ///
+    /// [`ValueRef::Array`]: crate::value::ValueRef::Array
+    fn array_ref(
+        &mut self,
+        is_le: bool,
+        variant_depth: u8,
+        container_depth: u8,
+        type_: &Type,
+    ) -> DecodeResult<ValueRef<'a>> {
+        let container_depth = self.container_depth(container_depth)?;
+        let array_size = self.u_32(is_le)?;
+        if self.options.maximum_array_length < array_size as usize {
+            return Err(DecodeError::ArrayTooBig(array_size));
+        }
+
+        if let Type::Byte = type_ {
+            // A byte array references the bytes directly.
+            let bytes = self.read(array_size as usize)?;
+            return Ok(ValueRef::ByteArray(bytes));
+        }
+
+        self.algin(type_.get_alignment())?;
+        let mut array = Vec::new();
+        let end = Decoder::<'a>::checked_add(self.offset, array_size as usize)?;
+        self.path.push(DecodePathElement::ArrayElement(0));
+        while self.offset < end {
+            if let Some(path_element) = self.path.last_mut() {
+                *path_element = DecodePathElement::ArrayElement(array.len());
+            }
+            let value = self.value_ref(is_le, variant_depth, container_depth, type_)?;
+            array.push(value);
+        }
+        self.path.pop();
+
+        if self.offset == end {
+            let array = ArrayRef {
+                type_: type_.clone(),
+                array,
+            };
+            Ok(ValueRef::Array(array))
+        } else {
+            Err(DecodeError::ArrayInvalidLength(self.offset, end))
+        }
+    }
+
+    /// Decode from a byte array at a specific offset to a [`ValueRef::Struct`].
+    ///
+    /// [`ValueRef::Struct`]: crate::value::ValueRef::Struct
+    fn struct_ref(
+        &mut self,
+        is_le: bool,
+        variant_depth: u8,
+        container_depth: u8,
+        signature: &[Type],
+    ) -> DecodeResult<ValueRef<'a>> {
+        let container_depth = self.container_depth(container_depth)?;
+        self.algin(8)?;
+        let values = self.values_ref(
+            is_le,
+            variant_depth,
+            container_depth,
+            signature,
+            DecodePathElement::StructField,
+        )?;
+        Ok(ValueRef::Struct(values))
+    }
+
+    /// Decode from a byte array at a specific offset to a [`ValueRef::DictEntry`].
+    ///
+    /// [`ValueRef::DictEntry`]: crate::value::ValueRef::DictEntry
+    fn dict_entry_ref(
+        &mut self,
+        is_le: bool,
+        variant_depth: u8,
+        container_depth: u8,
+        key_type: &Type,
+        value_type: &Type,
+    ) -> DecodeResult<ValueRef<'a>> {
+        let container_depth = self.container_depth(container_depth)?;
+        self.algin(8)?;
+        self.path.push(DecodePathElement::DictKey);
+        let key = self.value_ref(is_le, variant_depth, container_depth, key_type)?;
+        self.path.pop();
+        self.path.push(DecodePathElement::DictValue);
+        let value = self.value_ref(is_le, variant_depth, container_depth, value_type)?;
+        self.path.pop();
+        Ok(ValueRef::DictEntry(Box::new((key, value))))
+    }
+}
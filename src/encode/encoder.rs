@@ -1,9 +1,12 @@
 use bytes::{BufMut, BytesMut};
 #[cfg(target_family = "unix")]
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{BorrowedFd, RawFd};
 
 pub struct Encoder {
     pub(crate) buf: BytesMut,
+    /// The offset in the buffer, where the message starts. The alignment is relative to it.
+    pub(crate) start: usize,
+    /// The FDs, which are owned by the encoded values.
     #[cfg(target_family = "unix")]
     pub(crate) fds: Vec<RawFd>,
 }
@@ -11,7 +14,7 @@ pub struct Encoder {
 impl Encoder {
     /// This is a helper function to add the algin to the buffer.
     pub(crate) fn algin(&mut self, a: usize) {
-        let remain = self.buf.len() % a;
+        let remain = (self.buf.len() - self.start) % a;
         if remain != 0 {
             let padding_length = a - remain;
             self.buf.reserve(padding_length);
@@ -23,10 +26,26 @@ impl Encoder {
     }
 
     pub fn new() -> Encoder {
+        Encoder::with_buf(BytesMut::new())
+    }
+
+    /// Create an [`Encoder`], which appends to the given buffer.
+    pub(crate) fn with_buf(buf: BytesMut) -> Encoder {
         Encoder {
-            buf: BytesMut::new(),
+            start: buf.len(),
+            buf,
             #[cfg(target_family = "unix")]
             fds: Vec::new(),
         }
     }
 }
+
+/// Borrow the FDs, which were collected during the encoding.
+///
+/// # Safety
+/// The FDs have to be owned by the encoded values, which have to live at least for `'a`.
+#[cfg(target_family = "unix")]
+pub(crate) unsafe fn borrow_fds<'a>(fds: Vec<RawFd>) -> impl Iterator<Item = BorrowedFd<'a>> {
+    fds.into_iter()
+        .map(|fd| unsafe { BorrowedFd::borrow_raw(fd) })
+}
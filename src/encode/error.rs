@@ -1,4 +1,8 @@
-use crate::value::{Type, TypeError, MAXIMUM_ARRAY_LENGTH};
+use crate::{
+    decode::DecodeError,
+    message::MAXIMUM_MESSAGE_LENGTH,
+    value::{Type, TypeError, MAXIMUM_ARRAY_LENGTH},
+};
 use thiserror::Error;
 
 pub type EncodeResult<T> = Result<T, EncodeError>;
@@ -14,4 +18,31 @@ pub enum EncodeError {
     BodySignatureMissing(u32),
     #[error("Could not encode Signature: {0}")]
     SignatureError(#[from] TypeError),
+    #[error("Message is too long: {MAXIMUM_MESSAGE_LENGTH} < {0}")]
+    MessageTooLong(usize),
+    #[error("Major protocol version is not supported: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Maybe type is not supported by the D-Bus format")]
+    MaybeType,
+    #[error("String contains a null byte: {0:?}")]
+    StringNul(String),
+    #[error("Code of an unknown header field is invalid or of a known header field: {0}")]
+    HeaderFieldCode(u8),
+    #[error("Serial must not be zero")]
+    SerialZero,
+    #[error("Body contains attached FDs and indices of FDs")]
+    MixedUnixFds,
+    #[error("Index of a FD is not smaller than the UNIX_FDS header field: {0} >= {1}")]
+    UnixFdIndex(u32, u32),
+}
+
+/// An enum representing all errors, which can occur during the patching of an encoded message.
+#[derive(Debug, PartialEq, Error)]
+pub enum PatchError {
+    #[error("Could not decode the message: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Could not encode the message: {0}")]
+    EncodeError(#[from] EncodeError),
+    #[error("Header field occurs multiple times: {0}")]
+    DuplicateHeaderField(u8),
 }
@@ -0,0 +1,67 @@
+use crate::value::gvariant_offset_size;
+use bytes::{BufMut, BytesMut};
+#[cfg(target_family = "unix")]
+use std::os::unix::io::RawFd;
+
+/// An encoder for the [GVariant format].
+///
+/// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+pub(crate) struct GVariantEncoder {
+    pub(crate) buf: BytesMut,
+    /// The offset in the buffer, where the value starts. The alignment is relative to it.
+    pub(crate) start: usize,
+    pub(crate) is_le: bool,
+    /// The FDs of the message. If there are no FDs, then the handles are encoded as they are.
+    #[cfg(target_family = "unix")]
+    pub(crate) fds: Option<Vec<RawFd>>,
+}
+
+impl GVariantEncoder {
+    pub(crate) fn new(is_le: bool) -> GVariantEncoder {
+        GVariantEncoder {
+            buf: BytesMut::new(),
+            start: 0,
+            is_le,
+            #[cfg(target_family = "unix")]
+            fds: None,
+        }
+    }
+
+    #[cfg(target_family = "unix")]
+    pub(crate) fn with_fds(is_le: bool) -> GVariantEncoder {
+        GVariantEncoder {
+            buf: BytesMut::new(),
+            start: 0,
+            is_le,
+            fds: Some(Vec::new()),
+        }
+    }
+
+    /// This is a helper function to add the algin to the buffer.
+    pub(crate) fn algin(&mut self, a: usize) {
+        let remain = (self.buf.len() - self.start) % a;
+        if remain != 0 {
+            let padding_length = a - remain;
+            self.buf.put_bytes(0, padding_length);
+        }
+    }
+
+    /// Append the framing offsets of a container, which starts at `container_start`. The size of
+    /// the framing offsets depends on the size of the whole container.
+    pub(crate) fn framing_offsets(&mut self, container_start: usize, framing_offsets: &[usize]) {
+        let framing_offsets_len = framing_offsets.len();
+        if framing_offsets_len == 0 {
+            return;
+        }
+
+        let size = self.buf.len() - container_start;
+        let mut offset_size = 1;
+        while offset_size < gvariant_offset_size(size + framing_offsets_len * offset_size) {
+            offset_size *= 2;
+        }
+        for framing_offset in framing_offsets {
+            let bytes = (*framing_offset as u64).to_le_bytes();
+            self.buf.put_slice(&bytes[..offset_size]);
+        }
+    }
+}
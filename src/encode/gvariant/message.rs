@@ -0,0 +1,162 @@
+#[cfg(target_family = "unix")]
+use crate::encode::encoder::borrow_fds;
+use crate::encode::{
+    gvariant::GVariantEncoder, message::check_body_unix_fds, EncodeError, EncodeResult,
+};
+use crate::message::{is_unknown_code, Message, GVARIANT_PROTOCOL_VERSION, MAXIMUM_MESSAGE_LENGTH};
+use crate::value::{Array, Type, Value};
+use bytes::{BufMut, BytesMut};
+#[cfg(target_family = "unix")]
+use std::os::unix::io::BorrowedFd;
+
+impl GVariantEncoder {
+    /// Encode the header fields as `a{tv}`. The signature of the body is part of the body.
+    fn message_header_fields(
+        &mut self,
+        message: &Message,
+        #[cfg(target_family = "unix")] unix_fds: Option<u32>,
+    ) -> EncodeResult<()> {
+        let mut fields = message.header.fields.clone();
+        fields.signature = None;
+        if let Some((code, _)) = fields.unknown.iter().find(|(c, _)| !is_unknown_code(*c)) {
+            return Err(EncodeError::HeaderFieldCode(*code));
+        }
+        #[cfg(target_family = "unix")]
+        if unix_fds.is_some() {
+            fields.unix_fds = unix_fds;
+        }
+
+        let mut entries = Vec::new();
+        for field in Vec::<Value>::from(fields) {
+            if let Value::Struct(struct_) = field {
+                let mut values: Vec<Value> = struct_.into();
+                let value = values.pop().unwrap();
+                if let Some(Value::Byte(number)) = values.pop() {
+                    entries.push(Value::DictEntry(Box::new((
+                        Value::Uint64(number as u64),
+                        value,
+                    ))));
+                }
+            }
+        }
+        self.array(&Array {
+            type_: Type::DictEntry(Box::new((Type::Uint64, Type::Variant))),
+            array: entries,
+        })
+    }
+
+    /// Encode a message as `(yyyyuta{tv}v)`.
+    fn message(&mut self, message: &Message) -> EncodeResult<()> {
+        let header = &message.header;
+        if header.version != GVARIANT_PROTOCOL_VERSION {
+            return Err(EncodeError::UnsupportedVersion(header.version));
+        }
+        check_body_unix_fds(message)?;
+
+        // The body is encoded first to get the number of FDs. The body starts at an 8-byte
+        // boundary, therefore the alignment does not change.
+        let mut body_encoder = GVariantEncoder::new(self.is_le);
+        #[cfg(target_family = "unix")]
+        {
+            body_encoder.fds = self.fds.take();
+        }
+        let values: Vec<&Value> = message.body.iter().collect();
+        body_encoder.struct_values(&values)?;
+        let body_signature = Type::from_signature_to_string(&message.get_signature()?)?;
+        #[cfg(target_family = "unix")]
+        let unix_fds = match &body_encoder.fds {
+            Some(fds) if !fds.is_empty() => Some(fds.len() as u32),
+            _ => None,
+        };
+        #[cfg(target_family = "unix")]
+        {
+            self.fds = body_encoder.fds.take();
+        }
+
+        // Endianness flag
+        if self.is_le {
+            self.buf.put_u8(0x6c)
+        } else {
+            self.buf.put_u8(0x42)
+        }
+        // Message type
+        self.buf.put_u8(header.message_type as u8);
+        // Message flags
+        self.buf.put_u8(header.message_flags.bits());
+        // Major protocol version
+        self.buf.put_u8(header.version);
+        // Reserved
+        encode_number!(self, 0, u32);
+        encode_number!(self, header.serial as u64, u64);
+
+        self.message_header_fields(
+            message,
+            #[cfg(target_family = "unix")]
+            unix_fds,
+        )?;
+        let fields_end = self.buf.len() - self.start;
+
+        // The body is a variant of a struct.
+        self.algin(8);
+        self.buf.put_slice(&body_encoder.buf);
+        self.buf.put_u8(0);
+        self.buf.put_u8(b'(');
+        self.buf.put_slice(body_signature.as_bytes());
+        self.buf.put_u8(b')');
+
+        // Only the header fields have a framing offset, because the body is the last member.
+        self.framing_offsets(self.start, &[fields_end]);
+
+        let length = self.buf.len() - self.start;
+        if MAXIMUM_MESSAGE_LENGTH < length {
+            return Err(EncodeError::MessageTooLong(length));
+        }
+        Ok(())
+    }
+}
+
+impl Message {
+    /// Encode the [`Message`] in the [GVariant format], which is used by the major protocol
+    /// version 2. The [`Message`] has to have this version, see [`set_version`].
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::message::{Message, GVARIANT_PROTOCOL_VERSION};
+    /// # use dbus_message_parser::value::Value;
+    /// # use std::convert::TryInto;
+    /// let mut msg = Message::signal(
+    ///     "/object/path".try_into().unwrap(),
+    ///     "interface.name".try_into().unwrap(),
+    ///     "SignalName".try_into().unwrap(),
+    /// );
+    /// msg.add_value(Value::String("text".to_string()));
+    /// msg.set_version(GVARIANT_PROTOCOL_VERSION);
+    ///
+    /// let bytes = msg.encode_gvariant().unwrap();
+    /// assert_eq!(Message::decode_gvariant(bytes.freeze()), Ok(msg));
+    /// ```
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    /// [`set_version`]: Message::set_version
+    pub fn encode_gvariant(&self) -> EncodeResult<BytesMut> {
+        #[cfg(target_family = "unix")]
+        let mut encoder = GVariantEncoder::with_fds(self.header.is_le);
+        #[cfg(not(target_family = "unix"))]
+        let mut encoder = GVariantEncoder::new(self.header.is_le);
+        encoder.message(self)?;
+        Ok(encoder.buf)
+    }
+
+    /// Encode the [`Message`] in the [GVariant format] and returns the FDs, which are borrowed
+    /// from the values of the body. The handles in the body are indices into the FDs.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    #[cfg(target_family = "unix")]
+    pub fn encode_gvariant_with_fds(&self) -> EncodeResult<(BytesMut, Vec<BorrowedFd<'_>>)> {
+        let mut encoder = GVariantEncoder::with_fds(self.header.is_le);
+        encoder.message(self)?;
+        // SAFETY: The FDs are owned by the values of the body.
+        let fds = unsafe { borrow_fds(encoder.fds.unwrap_or_default()) }.collect();
+        Ok((encoder.buf, fds))
+    }
+}
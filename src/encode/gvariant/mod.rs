@@ -0,0 +1,17 @@
+macro_rules! encode_number {
+    ($encoder:ident, $n:expr, $type:ty) => {{
+        let n: $type = $n;
+        $encoder.algin(std::mem::size_of::<$type>());
+        if $encoder.is_le {
+            $encoder.buf.put_slice(&n.to_le_bytes());
+        } else {
+            $encoder.buf.put_slice(&n.to_be_bytes());
+        }
+    }};
+}
+
+mod encoder;
+mod message;
+mod value;
+
+pub(crate) use encoder::GVariantEncoder;
@@ -0,0 +1,172 @@
+use crate::encode::{gvariant::GVariantEncoder, EncodeError, EncodeResult};
+use crate::value::{gvariant_struct_alignment, Array, Maybe, Type, Value};
+use bytes::{BufMut, BytesMut};
+#[cfg(target_family = "unix")]
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd};
+
+impl GVariantEncoder {
+    /// Encode a string with a null byte at the end. The string must not contain a null byte.
+    fn string(&mut self, s: &str) -> EncodeResult<()> {
+        if s.contains('\0') {
+            return Err(EncodeError::StringNul(s.to_owned()));
+        }
+        self.buf.put_slice(s.as_bytes());
+        self.buf.put_u8(0);
+        Ok(())
+    }
+
+    #[cfg(target_family = "unix")]
+    fn unix_fd(&mut self, fd: BorrowedFd<'_>) {
+        let fd = fd.as_raw_fd();
+        let handle = match &mut self.fds {
+            Some(fds) => {
+                if let Some(i) = fds.iter().position(|i| *i == fd) {
+                    i as i32
+                } else {
+                    fds.push(fd);
+                    (fds.len() - 1) as i32
+                }
+            }
+            None => fd,
+        };
+        encode_number!(self, handle, i32);
+    }
+
+    fn variant(&mut self, value: &Value) -> EncodeResult<()> {
+        self.algin(8);
+        // The value is followed by a null byte and the type string.
+        self.value(value)?;
+        self.buf.put_u8(0);
+        let type_ = value.get_type()?;
+        self.buf.put_slice(type_.to_string().as_bytes());
+        Ok(())
+    }
+
+    /// Encode a maybe value. A value with a variable size is followed by a null byte.
+    fn maybe(&mut self, maybe: &Maybe) -> EncodeResult<()> {
+        if let Some(value) = maybe.get_value() {
+            self.value(value)?;
+            if maybe.get_type().get_gvariant_fixed_size().is_none() {
+                self.buf.put_u8(0);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn array(&mut self, array: &Array) -> EncodeResult<()> {
+        let type_ = array.get_type();
+        let alignment = type_.get_gvariant_alignment();
+        let is_fixed = type_.get_gvariant_fixed_size().is_some();
+        self.algin(alignment);
+
+        // The end of every element is stored as framing offset, if the elements do not have a
+        // fixed size.
+        let container_start = self.buf.len();
+        let mut framing_offsets = Vec::new();
+        for value in array.as_ref() {
+            self.algin(alignment);
+            self.value(value)?;
+            if !is_fixed {
+                framing_offsets.push(self.buf.len() - container_start);
+            }
+        }
+        self.framing_offsets(container_start, &framing_offsets);
+        Ok(())
+    }
+
+    /// Encode the members of a struct or a dict entry.
+    pub(super) fn struct_values(&mut self, values: &[&Value]) -> EncodeResult<()> {
+        let types = values
+            .iter()
+            .map(|value| value.get_type())
+            .collect::<Result<Vec<Type>, _>>()?;
+        let alignment = gvariant_struct_alignment(types.iter());
+        self.algin(alignment);
+
+        if values.is_empty() {
+            // The unit type is encoded as a single null byte.
+            self.buf.put_u8(0);
+            return Ok(());
+        }
+
+        // The end of every member with a variable size, except the last one, is stored as framing
+        // offset in reverse order.
+        let container_start = self.buf.len();
+        let mut is_fixed = true;
+        let mut framing_offsets = Vec::new();
+        for (i, (value, type_)) in values.iter().zip(types.iter()).enumerate() {
+            self.algin(type_.get_gvariant_alignment());
+            self.value(value)?;
+            if type_.get_gvariant_fixed_size().is_none() {
+                is_fixed = false;
+                if i + 1 != values.len() {
+                    framing_offsets.push(self.buf.len() - container_start);
+                }
+            }
+        }
+
+        if is_fixed {
+            // A struct with a fixed size is padded to its alignment.
+            self.algin(alignment);
+        } else {
+            framing_offsets.reverse();
+            self.framing_offsets(container_start, &framing_offsets);
+        }
+        Ok(())
+    }
+
+    /// Apply the alignment and encode the value into the buffer.
+    pub(crate) fn value(&mut self, value: &Value) -> EncodeResult<()> {
+        match value {
+            Value::Byte(b) => self.buf.put_u8(*b),
+            Value::Boolean(b) => self.buf.put_u8(*b as u8),
+            Value::Int16(i) => encode_number!(self, *i, i16),
+            Value::Uint16(u) => encode_number!(self, *u, u16),
+            Value::Int32(i) => encode_number!(self, *i, i32),
+            Value::Uint32(u) => encode_number!(self, *u, u32),
+            Value::Int64(i) => encode_number!(self, *i, i64),
+            Value::Uint64(u) => encode_number!(self, *u, u64),
+            Value::Double(f) => encode_number!(self, *f, f64),
+            #[cfg(target_family = "unix")]
+            Value::UnixFD(fd) => self.unix_fd(fd.as_fd()),
+            Value::UnixFdIndex(i) => encode_number!(self, *i as i32, i32),
+            Value::String(s) => self.string(s)?,
+            Value::ObjectPath(object_path) => self.string(object_path.as_ref())?,
+            Value::Signature(signature) => {
+                let signature = Type::from_signature_to_string(signature)?;
+                self.string(&signature)?
+            }
+            Value::Variant(value) => self.variant(value)?,
+            Value::Array(array) => self.array(array)?,
+            Value::Struct(struct_) => {
+                let values: Vec<&Value> = struct_.as_ref().iter().collect();
+                self.struct_values(&values)?
+            }
+            Value::DictEntry(dict_entry) => self.struct_values(&[&dict_entry.0, &dict_entry.1])?,
+            Value::Maybe(maybe) => self.maybe(maybe)?,
+        }
+        Ok(())
+    }
+}
+
+impl Value {
+    /// Encode the [`Value`] in the [GVariant format], for example to store it in a dconf database.
+    ///
+    /// The number of the FD of a [`Value::UnixFD`] is encoded as handle as it is and the index of a
+    /// [`Value::UnixFdIndex`] is encoded as handle.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::value::{Type, Value};
+    /// let value = Value::String("text".to_string());
+    /// let bytes = value.encode_gvariant(true).unwrap();
+    /// assert_eq!(&bytes[..], b"text\0");
+    /// ```
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub fn encode_gvariant(&self, is_le: bool) -> EncodeResult<BytesMut> {
+        let mut encoder = GVariantEncoder::new(is_le);
+        encoder.value(self)?;
+        Ok(encoder.buf)
+    }
+}
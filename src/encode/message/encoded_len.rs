@@ -0,0 +1,86 @@
+use crate::encode::value::algin;
+use crate::message::{Message, MINIMUM_HEADER_LENGTH};
+use crate::value::Type;
+
+/// Returns the offset after the number and the signature of a header field, which has a signature
+/// with a single type.
+#[inline]
+const fn header_field_end(offset: usize) -> usize {
+    algin(offset, 8) + 4
+}
+
+#[inline]
+const fn string_end(offset: usize, string_len: usize) -> usize {
+    algin(offset, 4) + 4 + string_len + 1
+}
+
+macro_rules! header_field_string {
+    ($offset:ident, $fields:ident, $field:ident) => {
+        if let Some(v) = &$fields.$field {
+            let s: &str = v.as_ref();
+            $offset = string_end(header_field_end($offset), s.len());
+        }
+    };
+}
+
+impl Message {
+    /// Returns the number of bytes of the encoded [`Message`] including the padding, without
+    /// encoding it.
+    ///
+    /// The [`Message`] is not checked, so the length is only meaningful for a [`Message`], which
+    /// passes [`validate`]. For a [`Message`], which cannot be encoded, a length is returned
+    /// anyway, for example if the body is too long or contains a maybe value.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::message::Message;
+    /// # use dbus_message_parser::value::Value;
+    /// # use std::convert::TryInto;
+    /// let mut msg = Message::signal(
+    ///     "/object/path".try_into().unwrap(),
+    ///     "interface.name".try_into().unwrap(),
+    ///     "SignalName".try_into().unwrap(),
+    /// );
+    /// msg.add_value(Value::String("Hello".to_string()));
+    ///
+    /// assert_eq!(msg.encoded_len(), msg.encode().unwrap().len());
+    /// ```
+    ///
+    /// [`validate`]: Message::validate
+    pub fn encoded_len(&self) -> usize {
+        let fields = &self.header.fields;
+
+        // The header fields array starts after the fixed part of the header and its length. It
+        // is already aligned to 8.
+        let mut offset = MINIMUM_HEADER_LENGTH;
+        header_field_string!(offset, fields, path);
+        header_field_string!(offset, fields, interface);
+        header_field_string!(offset, fields, member);
+        header_field_string!(offset, fields, error_name);
+        if fields.reply_serial.is_some() {
+            offset = algin(header_field_end(offset), 4) + 4;
+        }
+        header_field_string!(offset, fields, destination);
+        header_field_string!(offset, fields, sender);
+        let body_signature_len: usize = self.body.iter().map(|v| v.signature_len()).sum();
+        if body_signature_len != 0 {
+            offset = header_field_end(offset) + 1 + body_signature_len + 1;
+        } else if let Some(signature) = &fields.signature {
+            let signature_len: usize = signature.iter().map(Type::signature_len).sum();
+            offset = header_field_end(offset) + 1 + signature_len + 1;
+        }
+        if fields.unix_fds.is_some() || self.body.iter().any(|v| v.has_unix_fds()) {
+            offset = algin(header_field_end(offset), 4) + 4;
+        }
+        for (_, value) in &fields.unknown {
+            offset = algin(offset, 8) + 1;
+            offset = value.encoded_end(offset + 1 + value.signature_len() + 1);
+        }
+
+        // The body starts at an 8-byte boundary.
+        let offset = algin(offset, 8);
+        self.body
+            .iter()
+            .fold(offset, |offset, value| value.encoded_end(offset))
+    }
+}
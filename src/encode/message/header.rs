@@ -1,22 +1,59 @@
 use crate::{
-    encode::{EncodeResult, Encoder},
-    message::MessageHeader,
-    value::{Array, Type, Value},
+    encode::{EncodeError, EncodeResult, Encoder},
+    message::{is_unknown_code, MessageHeader, MAJOR_PROTOCOL_VERSION, MAXIMUM_MESSAGE_LENGTH},
+    value::MAXIMUM_ARRAY_LENGTH,
 };
-use cfg_if::cfg_if;
-use lazy_static::lazy_static;
-use std::convert::TryInto;
 
-lazy_static! {
-    static ref ARRAY_TYPE: Type = "(yv)".try_into().unwrap();
+macro_rules! header_field_string {
+    ($encoder:ident, $fields:ident, $field:ident, $number:literal, $is_le:ident) => {
+        if let Some(v) = &$fields.$field {
+            $encoder.message_header_field($number, "s");
+            $encoder.string_unchecked(v.as_ref(), $is_le);
+        }
+    };
 }
 
 impl Encoder {
-    pub fn message_header(
+    /// Check the length of the whole message, which starts at `start`, before the body is
+    /// appended.
+    pub(crate) fn message_check_length(
+        &self,
+        start: usize,
+        body_length: usize,
+    ) -> EncodeResult<()> {
+        let length = self.buf.len() - start + body_length;
+        if length <= MAXIMUM_MESSAGE_LENGTH {
+            Ok(())
+        } else {
+            Err(EncodeError::MessageTooLong(length))
+        }
+    }
+
+    /// Apply the alignment and encode the number and the signature of a header field.
+    pub(super) fn message_header_field(&mut self, number: u8, signature: &str) {
+        self.algin(8);
+        self.byte(number);
+        self.signature_string(signature);
+    }
+
+    /// Encode the header of a message.
+    ///
+    /// The length of the body is encoded as zero and has to be set afterwards. If `body_signature`
+    /// is empty, the signature of the header fields is used. If `unix_fds` is given, it is used
+    /// instead of the number of FDs of the header fields.
+    ///
+    /// Returns the offset of the length of the body and the offset of the number of FDs, if there
+    /// is one.
+    pub(crate) fn message_header(
         &mut self,
         message_header: &MessageHeader,
-        body: Option<(u32, Vec<Type>)>,
-    ) -> EncodeResult<()> {
+        body_signature: &str,
+        #[cfg(target_family = "unix")] unix_fds: Option<u32>,
+    ) -> EncodeResult<(usize, Option<usize>)> {
+        if message_header.version != MAJOR_PROTOCOL_VERSION {
+            return Err(EncodeError::UnsupportedVersion(message_header.version));
+        }
+
         let is_le = message_header.is_le;
 
         // Endianness flag
@@ -32,34 +69,76 @@ impl Encoder {
         // Major protocol version
         self.byte(message_header.version);
 
-        // Add the signature of the body to the header fields
-        let mut fields = message_header.fields.clone();
-        cfg_if! {
-            if #[cfg(target_family = "unix")] {
-                let fds_len = self.fds.len();
-                if fds_len != 0 {
-                    fields.unix_fds = Some(fds_len as u32);
-                }
-            }
+        // The length of the body is set afterwards.
+        let body_length_offset = self.buf.len();
+        self.u_32(0, is_le);
+        self.u_32(message_header.serial, is_le);
+
+        // Encode the header fields directly as an array of structs.
+        let fields = &message_header.fields;
+        let array_len_offset = self.buf.len();
+        self.u_32(0, is_le);
+        self.algin(8);
+        let array_len_offset_algin = self.buf.len();
+
+        if let Some(path) = &fields.path {
+            self.message_header_field(1, "o");
+            self.object_path(path, is_le);
+        }
+        header_field_string!(self, fields, interface, 2, is_le);
+        header_field_string!(self, fields, member, 3, is_le);
+        header_field_string!(self, fields, error_name, 4, is_le);
+        if let Some(reply_serial) = fields.reply_serial {
+            self.message_header_field(5, "u");
+            self.uint_32(reply_serial, is_le);
         }
-        let body_length = if let Some((body_length, body_signature)) = body {
-            fields.signature = Some(body_signature);
-            body_length
+        header_field_string!(self, fields, destination, 6, is_le);
+        header_field_string!(self, fields, sender, 7, is_le);
+        if !body_signature.is_empty() {
+            self.message_header_field(8, "g");
+            self.signature_string(body_signature);
+        } else if let Some(signature) = &fields.signature {
+            self.message_header_field(8, "g");
+            self.signature(signature)?;
+        }
+        #[cfg(not(target_family = "unix"))]
+        let unix_fds = None;
+        let unix_fds_offset = if let Some(unix_fds) = unix_fds.or(fields.unix_fds) {
+            self.message_header_field(9, "u");
+            self.uint_32(unix_fds, is_le);
+            Some(self.buf.len() - 4)
         } else {
-            0
+            None
         };
+        for (number, value) in &fields.unknown {
+            if !is_unknown_code(*number) {
+                return Err(EncodeError::HeaderFieldCode(*number));
+            }
+            self.algin(8);
+            self.byte(*number);
+            self.variant(value, is_le)?;
+        }
 
-        self.u_32(body_length, is_le);
-        self.u_32(message_header.serial, is_le);
+        let array_len = self.buf.len() - array_len_offset_algin;
+        if MAXIMUM_ARRAY_LENGTH < array_len {
+            return Err(EncodeError::ArrayTooBig(array_len));
+        }
+        self.set_uint_32(array_len as u32, array_len_offset, is_le);
 
-        // Encode the header fields.
-        let headers: Vec<Value> = fields.into();
-        let headers = Array {
-            array: headers,
-            type_: ARRAY_TYPE.clone(),
-        };
-        self.array(&headers, is_le)?;
+        Ok((body_length_offset, unix_fds_offset))
+    }
+}
 
+#[test]
+fn message_check_length() {
+    let mut encoder = Encoder::new();
+    encoder.buf.extend_from_slice(&[0; 16]);
+    assert_eq!(
+        encoder.message_check_length(0, MAXIMUM_MESSAGE_LENGTH - 16),
         Ok(())
-    }
+    );
+    assert_eq!(
+        encoder.message_check_length(0, MAXIMUM_MESSAGE_LENGTH),
+        Err(EncodeError::MessageTooLong(MAXIMUM_MESSAGE_LENGTH + 16))
+    );
 }
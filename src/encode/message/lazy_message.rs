@@ -0,0 +1,71 @@
+#[cfg(target_family = "unix")]
+use crate::encode::encoder::borrow_fds;
+use crate::encode::{EncodeError, EncodeResult, Encoder};
+use crate::message::LazyMessage;
+use crate::value::Type;
+use bytes::BytesMut;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::{AsRawFd, BorrowedFd};
+
+impl Encoder {
+    /// Encode a [`LazyMessage`] object to a byte array. The raw bytes of the body are appended
+    /// without decoding them.
+    pub fn lazy_message(&mut self, message: &LazyMessage) -> EncodeResult<()> {
+        let start = self.start;
+        let is_le = message.header.is_le;
+
+        let body_length = message.body.len() as u32;
+        let body_signature = &message.signature;
+        if body_length == 0 {
+            if !body_signature.is_empty() {
+                return Err(EncodeError::BodyLengthZero(body_signature.clone()));
+            }
+        } else if body_signature.is_empty() {
+            return Err(EncodeError::BodySignatureMissing(body_length));
+        }
+        if body_signature.iter().any(Type::contains_maybe) {
+            return Err(EncodeError::MaybeType);
+        }
+        let body_signature = Type::from_signature_to_string(body_signature)?;
+
+        #[cfg(target_family = "unix")]
+        let (body_length_offset, _) = {
+            self.fds.extend(message.fds.iter().map(AsRawFd::as_raw_fd));
+            let unix_fds = if message.fds.is_empty() {
+                None
+            } else {
+                Some(message.fds.len() as u32)
+            };
+            self.message_header(&message.header, &body_signature, unix_fds)?
+        };
+        #[cfg(not(target_family = "unix"))]
+        let (body_length_offset, _) = self.message_header(&message.header, &body_signature)?;
+        self.set_uint_32(body_length, body_length_offset, is_le);
+
+        // Append the body.
+        self.algin(8);
+        self.message_check_length(start, message.body.len())?;
+        self.buf.extend_from_slice(&message.body);
+
+        Ok(())
+    }
+}
+
+impl LazyMessage {
+    pub fn encode(&self) -> EncodeResult<BytesMut> {
+        let mut encoder = Encoder::new();
+        encoder.lazy_message(self)?;
+        Ok(encoder.buf)
+    }
+
+    /// Encode the [`LazyMessage`] and returns the FDs of the [`LazyMessage`], which are
+    /// borrowed from it.
+    #[cfg(target_family = "unix")]
+    pub fn encode_with_fds(&self) -> EncodeResult<(BytesMut, Vec<BorrowedFd<'_>>)> {
+        let mut encoder = Encoder::new();
+        encoder.lazy_message(self)?;
+        // SAFETY: The FDs are owned by the `LazyMessage`.
+        let fds = unsafe { borrow_fds(encoder.fds) }.collect();
+        Ok((encoder.buf, fds))
+    }
+}
@@ -1,49 +1,78 @@
+#[cfg(target_family = "unix")]
+use crate::encode::encoder::borrow_fds;
 use crate::encode::{EncodeError, EncodeResult, Encoder};
 use crate::message::Message;
-use crate::value::Type;
 use bytes::BytesMut;
-use cfg_if::cfg_if;
+use std::mem::take;
 #[cfg(target_family = "unix")]
-use std::os::unix::io::RawFd;
+use std::os::unix::io::BorrowedFd;
+
+/// Check the FDs of the body of the `message`.
+///
+/// The body must not contain attached FDs and indices of FDs at the same time, because the indices
+/// of the attached FDs would collide with the other indices. Every index must be smaller than the
+/// [`unix_fds`] header field.
+///
+/// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
+pub(crate) fn check_body_unix_fds(message: &Message) -> EncodeResult<()> {
+    let indices = message.get_body_fd_indices();
+    #[cfg(target_family = "unix")]
+    if !indices.is_empty() && message.body.iter().any(|v| v.has_unix_fds()) {
+        return Err(EncodeError::MixedUnixFds);
+    }
+    let unix_fds = message.get_unix_fds().unwrap_or(0);
+    match indices.into_iter().find(|i| unix_fds <= *i) {
+        Some(i) => Err(EncodeError::UnixFdIndex(i, unix_fds)),
+        None => Ok(()),
+    }
+}
 
 impl Encoder {
     /// Encode a `Message` object to a byte array.
+    ///
+    /// The header and the body are encoded in a single pass. The length of the body and the
+    /// number of FDs are set after the body is encoded.
     pub fn message(&mut self, message: &Message) -> EncodeResult<()> {
+        let start = self.start;
         let is_le = message.header.is_le;
-        // Encode the body in another buffer
-        let mut encoder = Encoder::new();
+        check_body_unix_fds(message)?;
+
         let mut body_signature = String::new();
         for v in &message.body {
             v.to_signature_string(&mut body_signature, 0, 0, 0)?;
-            encoder.value(v, is_le)?;
         }
 
-        cfg_if! {
-            if #[cfg(target_family = "unix")] {
-                self.fds = encoder.fds;
-            }
-        }
-
-        let body = encoder.buf;
-        let body_length = body.len() as u32;
-        if body_length == 0 {
-            if body_signature.is_empty() {
-                self.message_header(&message.header, None)?;
+        #[cfg(target_family = "unix")]
+        let (body_length_offset, unix_fds_offset) = {
+            // The number of FDs is only known after the body is encoded. Without attached FDs,
+            // the number of the header fields is kept.
+            if message.body.iter().any(|v| v.has_unix_fds()) {
+                self.message_header(&message.header, &body_signature, Some(0))?
             } else {
-                let body_signature = Type::from_string_to_signature(&body_signature)?;
-                return Err(EncodeError::BodyLengthZero(body_signature));
+                let (body_length_offset, _) =
+                    self.message_header(&message.header, &body_signature, None)?;
+                (body_length_offset, None)
             }
-        } else if body_signature.is_empty() {
-            return Err(EncodeError::BodySignatureMissing(body_length));
-        } else {
-            let body_signature = Type::from_string_to_signature(&body_signature)?;
-            let body = Some((body_length, body_signature));
-            self.message_header(&message.header, body)?;
-        }
+        };
+        #[cfg(not(target_family = "unix"))]
+        let (body_length_offset, _) = self.message_header(&message.header, &body_signature)?;
 
-        // Append the body.
+        // Encode the body.
         self.algin(8);
-        self.buf.extend(body);
+        let body_offset = self.buf.len();
+        for v in &message.body {
+            self.value(v, is_le)?;
+        }
+        self.message_check_length(start, 0)?;
+
+        let body_length = self.buf.len() - body_offset;
+        self.set_uint_32(body_length as u32, body_length_offset, is_le);
+
+        #[cfg(target_family = "unix")]
+        if let Some(unix_fds_offset) = unix_fds_offset {
+            let unix_fds = self.fds.len();
+            self.set_uint_32(unix_fds as u32, unix_fds_offset, is_le);
+        }
 
         Ok(())
     }
@@ -51,16 +80,73 @@ impl Encoder {
 
 impl Message {
     pub fn encode(&self) -> EncodeResult<BytesMut> {
-        let mut encoder = Encoder::new();
-        encoder.message(self)?;
-        Ok(encoder.buf)
+        let mut buf = BytesMut::new();
+        self.encode_into(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Encode the [`Message`] and append it to `buf`.
+    ///
+    /// The header and the body are encoded in a single pass directly into `buf`, so the same
+    /// buffer can be reused for many messages. If an error occurs, `buf` is not changed.
+    ///
+    /// # Example
+    /// ```
+    /// # use bytes::BytesMut;
+    /// # use dbus_message_parser::message::Message;
+    /// # use std::convert::TryInto;
+    /// let msg = Message::signal(
+    ///     "/object/path".try_into().unwrap(),
+    ///     "interface.name".try_into().unwrap(),
+    ///     "SignalName".try_into().unwrap(),
+    /// );
+    ///
+    /// let mut buf = BytesMut::new();
+    /// msg.encode_into(&mut buf).unwrap();
+    /// msg.encode_into(&mut buf).unwrap();
+    /// assert_eq!(&buf[..buf.len() / 2], &msg.encode().unwrap()[..]);
+    /// ```
+    pub fn encode_into(&self, buf: &mut BytesMut) -> EncodeResult<()> {
+        let start = buf.len();
+        let mut encoder = Encoder::with_buf(take(buf));
+        let result = encoder.message(self);
+        *buf = encoder.buf;
+        if result.is_err() {
+            buf.truncate(start);
+        }
+        result
+    }
+
+    /// Encode the [`Message`] and returns the FDs of the [`Message`], which are borrowed from the
+    /// values of the body.
+    #[cfg(target_family = "unix")]
+    pub fn encode_with_fds(&self) -> EncodeResult<(BytesMut, Vec<BorrowedFd<'_>>)> {
+        let mut buf = BytesMut::new();
+        let mut fds = Vec::new();
+        self.encode_into_with_fds(&mut buf, &mut fds)?;
+        Ok((buf, fds))
     }
 
+    /// Encode the [`Message`] and append it to `buf` and the FDs of the [`Message`] to `fds`.
+    ///
+    /// The FDs are borrowed from the values of the body. If an error occurs, `buf` and `fds` are
+    /// not changed.
     #[cfg(target_family = "unix")]
-    pub fn encode_with_fds(&self) -> EncodeResult<(BytesMut, Vec<RawFd>)> {
-        let mut encoder = Encoder::new();
-        encoder.message(self)?;
-        let result = (encoder.buf, encoder.fds);
-        Ok(result)
+    pub fn encode_into_with_fds<'a>(
+        &'a self,
+        buf: &mut BytesMut,
+        fds: &mut Vec<BorrowedFd<'a>>,
+    ) -> EncodeResult<()> {
+        let start = buf.len();
+        let mut encoder = Encoder::with_buf(take(buf));
+        let result = encoder.message(self);
+        *buf = encoder.buf;
+        if result.is_ok() {
+            // SAFETY: The FDs are owned by the values of the body, which live for `'a`.
+            fds.extend(unsafe { borrow_fds(encoder.fds) });
+        } else {
+            buf.truncate(start);
+        }
+        result
     }
 }
@@ -1,2 +1,7 @@
+mod encoded_len;
 mod header;
+mod lazy_message;
 mod message_struct;
+mod patch;
+
+pub(crate) use message_struct::check_body_unix_fds;
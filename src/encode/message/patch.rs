@@ -0,0 +1,128 @@
+use crate::{
+    decode::{DecodeError, Decoder},
+    encode::{EncodeError, Encoder, PatchError},
+    message::{Message, MAXIMUM_MESSAGE_LENGTH},
+    value::{Bus, MAXIMUM_ARRAY_LENGTH},
+};
+use bytes::BytesMut;
+
+impl Encoder {
+    /// Encode the message in `bytes` again, where the header field `number` is replaced by the
+    /// header field encoded by `value`. If the message does not have such a header field, then it is
+    /// appended to the header fields. The other header fields and the body are copied.
+    ///
+    /// If the header field `number` occurs multiple times, then a
+    /// [`PatchError::DuplicateHeaderField`] is returned.
+    fn message_patch_header_field<F>(
+        &mut self,
+        bytes: &[u8],
+        number: u8,
+        signature: &str,
+        value: F,
+    ) -> Result<(), PatchError>
+    where
+        F: FnOnce(&mut Encoder, bool),
+    {
+        let mut decoder = Decoder::new(bytes);
+        let (is_le, fields, (body_start, body_end)) = decoder.message_header_field_offsets()?;
+
+        // Copy the fixed part of the header.
+        self.buf.extend_from_slice(&bytes[..12]);
+        let array_len_offset = self.buf.len();
+        self.u_32(0, is_le);
+        self.algin(8);
+        let array_len_offset_algin = self.buf.len();
+
+        // Every header field starts at an 8-byte boundary, therefore the alignment of the copied
+        // header fields does not change.
+        let mut value = Some(value);
+        for (field_number, start, end) in fields {
+            if field_number == number {
+                match value.take() {
+                    Some(value) => {
+                        self.message_header_field(number, signature);
+                        value(self, is_le);
+                    }
+                    None => return Err(PatchError::DuplicateHeaderField(number)),
+                }
+            } else {
+                self.algin(8);
+                self.buf.extend_from_slice(&bytes[start..end]);
+            }
+        }
+        if let Some(value) = value {
+            self.message_header_field(number, signature);
+            value(self, is_le);
+        }
+
+        let array_len = self.buf.len() - array_len_offset_algin;
+        if MAXIMUM_ARRAY_LENGTH < array_len {
+            return Err(EncodeError::ArrayTooBig(array_len).into());
+        }
+        self.set_uint_32(array_len as u32, array_len_offset, is_le);
+
+        // The body starts at an 8-byte boundary.
+        self.algin(8);
+        let length = self.buf.len() - self.start + body_end - body_start;
+        if MAXIMUM_MESSAGE_LENGTH < length {
+            return Err(EncodeError::MessageTooLong(length).into());
+        }
+        self.buf.extend_from_slice(&bytes[body_start..body_end]);
+        Ok(())
+    }
+}
+
+impl Message {
+    /// Set the serial number of the encoded [`Message`] at the start of `bytes` in place, without
+    /// decoding it. The serial number must not be zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use bytes::Bytes;
+    /// # use dbus_message_parser::message::Message;
+    /// let mut msg = b"l\x02\x01\x01\n\0\0\0\x01\0\0\0=\0\0\0\x06\x01s\0\x05\0\0\0:1.98\0\0\0\x05\x01u\0\x01\0\0\0\x08\x01g\0\x01s\0\0\x07\x01s\0\x14\0\0\0org.freedesktop.DBus\0\0\0\0\x05\0\0\0:1.98\0".to_vec();
+    ///
+    /// Message::patch_serial(&mut msg, 10).unwrap();
+    /// let (msg, _) = Message::decode(Bytes::from(msg)).unwrap();
+    /// assert_eq!(msg.get_serial(), 10);
+    /// ```
+    pub fn patch_serial(bytes: &mut [u8], serial: u32) -> Result<(), PatchError> {
+        if serial == 0 {
+            return Err(EncodeError::SerialZero.into());
+        }
+
+        let mut decoder = Decoder::new(bytes);
+        let is_le = decoder.message_header_is_le()?;
+        let bytes_len = bytes.len();
+        if bytes_len < 12 {
+            return Err(DecodeError::NotEnoughBytes(bytes_len, 12).into());
+        }
+        let serial = if is_le {
+            serial.to_le_bytes()
+        } else {
+            serial.to_be_bytes()
+        };
+        bytes[8..12].copy_from_slice(&serial);
+        Ok(())
+    }
+
+    /// Encode the [`Message`] at the start of `bytes` again, where the `REPLY_SERIAL` header field
+    /// is set to `reply_serial`. The header field is replaced or inserted and the body is copied.
+    pub fn patch_reply_serial(bytes: &[u8], reply_serial: u32) -> Result<BytesMut, PatchError> {
+        let mut encoder = Encoder::new();
+        encoder.message_patch_header_field(bytes, 5, "u", |encoder, is_le| {
+            encoder.uint_32(reply_serial, is_le)
+        })?;
+        Ok(encoder.buf)
+    }
+
+    /// Encode the [`Message`] at the start of `bytes` again, where the `SENDER` header field is
+    /// set to `sender`. The header field is replaced or inserted and the body is copied.
+    pub fn patch_sender(bytes: &[u8], sender: &Bus) -> Result<BytesMut, PatchError> {
+        let mut encoder = Encoder::new();
+        encoder.message_patch_header_field(bytes, 7, "s", |encoder, is_le| {
+            encoder.string_unchecked(sender.as_ref(), is_le)
+        })?;
+        Ok(encoder.buf)
+    }
+}
@@ -1,7 +1,9 @@
 mod encoder;
 mod error;
+mod gvariant;
 mod message;
 mod value;
 
 pub(crate) use encoder::Encoder;
-pub use error::{EncodeError, EncodeResult};
+pub use error::{EncodeError, EncodeResult, PatchError};
+pub(crate) use value::algin;
@@ -1,9 +1,9 @@
-use crate::encode::{EncodeResult, Encoder};
+use crate::encode::{EncodeError, EncodeResult, Encoder};
 use crate::value::{ObjectPath, Type};
 use bytes::BufMut;
 use std::mem::size_of;
 #[cfg(target_family = "unix")]
-use std::os::unix::io::RawFd;
+use std::os::unix::io::{AsRawFd, BorrowedFd};
 
 impl Encoder {
     /// Encode a `u8` into the buffer.
@@ -90,11 +90,13 @@ impl Encoder {
         self.buf[offset + 3] = bytes[3];
     }
 
-    /// Apply the alignment and encode a [`RawFd`] into the buffer.
+    /// Apply the alignment and encode the index of a [`BorrowedFd`] into the buffer. The FD has
+    /// to be owned by the encoded value.
     ///
-    /// [`RawFd`]: std::os::unix::io::RawFd
+    /// [`BorrowedFd`]: std::os::unix::io::BorrowedFd
     #[cfg(target_family = "unix")]
-    pub fn unix_fd(&mut self, fd: RawFd, is_len: bool) {
+    pub fn unix_fd(&mut self, fd: BorrowedFd<'_>, is_len: bool) {
+        let fd = fd.as_raw_fd();
         let i = if let Some(i) = self.fds.iter().position(|i| *i == fd) {
             i
         } else {
@@ -138,7 +140,18 @@ impl Encoder {
     }
 
     /// Apply the alignment and encode a `&str` into the buffer and use 4 bytes.
-    pub fn string(&mut self, s: &str, is_le: bool) {
+    /// The string must not contain a null byte.
+    pub fn string(&mut self, s: &str, is_le: bool) -> EncodeResult<()> {
+        if s.contains('\0') {
+            return Err(EncodeError::StringNul(s.to_owned()));
+        }
+        self.string_unchecked(s, is_le);
+        Ok(())
+    }
+
+    /// Apply the alignment and encode a `&str`, which does not contain a null byte, into the
+    /// buffer and use 4 bytes.
+    pub(crate) fn string_unchecked(&mut self, s: &str, is_le: bool) {
         let string_len = s.len();
         self.uint_32(string_len as u32, is_le);
         self.buf.reserve(string_len + 1);
@@ -148,19 +161,28 @@ impl Encoder {
 
     /// Apply the alignment and encode an `&ObjectPath` into the buffer and use 4 bytes.
     pub fn object_path(&mut self, o: &ObjectPath, is_le: bool) {
-        self.string(o.as_ref(), is_le);
+        // An object path cannot contain a null byte.
+        self.string_unchecked(o.as_ref(), is_le);
     }
 
     /// Encode a `&Signature` into the buffer and use 1 bytes.
     pub fn signature(&mut self, signature: &[Type]) -> EncodeResult<()> {
+        if signature.iter().any(Type::contains_maybe) {
+            return Err(EncodeError::MaybeType);
+        }
         let signature_string = Type::from_signature_to_string(signature)?;
+        self.signature_string(&signature_string);
+        Ok(())
+    }
+
+    /// Encode a signature, which is already converted to a `&str`, into the buffer and use 1
+    /// bytes.
+    pub(crate) fn signature_string(&mut self, signature_string: &str) {
         let signature_len = signature_string.len();
 
         self.byte(signature_len as u8);
         self.buf.reserve(signature_len + 1);
         self.buf.put(signature_string.as_bytes());
         self.buf.put_u8(0);
-
-        Ok(())
     }
 }
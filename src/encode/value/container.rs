@@ -37,6 +37,7 @@ impl Encoder {
     }
 
     /// Apply the alignment and encode a `&(Value, Value)` as a dict entry into the buffer.
+    #[allow(clippy::borrow_deref_ref)]
     pub fn dict_entry(&mut self, b: &(Value, Value), is_le: bool) -> EncodeResult<()> {
         self.algin(8);
         let (key, value) = &*b;
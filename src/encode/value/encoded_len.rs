@@ -0,0 +1,103 @@
+use crate::value::{Type, Value};
+
+/// Returns the offset aligned to `a`, like [`Encoder::algin`] does.
+///
+/// [`Encoder::algin`]: crate::encode::Encoder::algin
+#[inline]
+pub(crate) const fn algin(offset: usize, a: usize) -> usize {
+    let remain = offset % a;
+    if remain == 0 {
+        offset
+    } else {
+        offset + a - remain
+    }
+}
+
+impl Type {
+    /// Returns the length of the signature string of the `Type`.
+    pub(crate) fn signature_len(&self) -> usize {
+        match self {
+            Type::Array(type_) => 1 + type_.signature_len(),
+            Type::Struct(types) => 2 + types.iter().map(Type::signature_len).sum::<usize>(),
+            Type::DictEntry(types) => 2 + types.0.signature_len() + types.1.signature_len(),
+            Type::Maybe(type_) => 1 + type_.signature_len(),
+            _ => 1,
+        }
+    }
+}
+
+impl Value {
+    /// Returns the length of the signature string of the `Value`.
+    pub(crate) fn signature_len(&self) -> usize {
+        match self {
+            Value::Array(array) => 1 + array.get_type().signature_len(),
+            Value::Struct(struct_) => {
+                2 + struct_
+                    .as_ref()
+                    .iter()
+                    .map(Value::signature_len)
+                    .sum::<usize>()
+            }
+            Value::DictEntry(b) => 2 + b.0.signature_len() + b.1.signature_len(),
+            Value::Maybe(maybe) => 1 + maybe.get_type().signature_len(),
+            _ => 1,
+        }
+    }
+
+    /// Returns the offset, where the encoded `Value` ends, if it is encoded at `offset`.
+    pub(crate) fn encoded_end(&self, offset: usize) -> usize {
+        match self {
+            Value::Byte(_) => offset + 1,
+            Value::Int16(_) | Value::Uint16(_) => algin(offset, 2) + 2,
+            Value::Boolean(_) | Value::Int32(_) | Value::Uint32(_) | Value::UnixFdIndex(_) => {
+                algin(offset, 4) + 4
+            }
+            #[cfg(target_family = "unix")]
+            Value::UnixFD(_) => algin(offset, 4) + 4,
+            Value::Int64(_) | Value::Uint64(_) | Value::Double(_) => algin(offset, 8) + 8,
+            Value::String(s) => algin(offset, 4) + 4 + s.len() + 1,
+            Value::ObjectPath(o) => algin(offset, 4) + 4 + o.as_ref().len() + 1,
+            Value::Signature(signature) => {
+                offset + 1 + signature.iter().map(Type::signature_len).sum::<usize>() + 1
+            }
+            Value::Array(array) => {
+                let offset = algin(offset, 4) + 4;
+                let offset = algin(offset, array.get_type().get_alignment());
+                array
+                    .as_ref()
+                    .iter()
+                    .fold(offset, |offset, value| value.encoded_end(offset))
+            }
+            Value::Struct(struct_) => struct_
+                .as_ref()
+                .iter()
+                .fold(algin(offset, 8), |offset, value| value.encoded_end(offset)),
+            Value::DictEntry(b) => {
+                let offset = b.0.encoded_end(algin(offset, 8));
+                b.1.encoded_end(offset)
+            }
+            Value::Variant(v) => v.encoded_end(offset + 1 + v.signature_len() + 1),
+            // A maybe value cannot be encoded in the D-Bus format.
+            Value::Maybe(_) => offset,
+        }
+    }
+
+    /// Returns the number of bytes of the encoded `Value` including the padding, if it is encoded
+    /// at `offset`. The offset is relative to the start of the message.
+    ///
+    /// The length does not depend on the endianness.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::value::Value;
+    /// let value = Value::Uint32(1);
+    /// assert_eq!(value.encoded_len(0), 4);
+    /// assert_eq!(value.encoded_len(1), 7);
+    ///
+    /// let value = Value::String("Hello".to_string());
+    /// assert_eq!(value.encoded_len(4), 10);
+    /// ```
+    pub fn encoded_len(&self, offset: usize) -> usize {
+        self.encoded_end(offset) - offset
+    }
+}
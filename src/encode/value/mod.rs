@@ -1,5 +1,8 @@
 mod basic_type;
 mod container;
+mod encoded_len;
 #[cfg(test)]
 mod tests;
 mod value_enum;
+
+pub(crate) use encoded_len::algin;
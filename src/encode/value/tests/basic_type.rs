@@ -1,6 +1,10 @@
-use crate::encode::Encoder;
+use crate::encode::{EncodeError, Encoder};
+#[cfg(target_family = "unix")]
+use crate::value::open_null;
 use crate::value::{Type, Value};
 use std::convert::TryInto;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::{AsRawFd, RawFd};
 
 macro_rules! init_test {
     ($array:tt, $value:expr, $le:expr) => {{
@@ -14,6 +18,14 @@ macro_rules! init_test {
     }};
 }
 
+#[cfg(target_family = "unix")]
+fn raw_fd(v: &Value) -> RawFd {
+    match v {
+        Value::UnixFD(fd) => fd.as_raw_fd(),
+        v => panic!("Value is not a FD: {:?}", v),
+    }
+}
+
 macro_rules! end_test {
     ($b:ident, $array:tt) => {
         assert_eq!(&$b[..], &$array[..]);
@@ -203,6 +215,16 @@ fn string_3() {
     end_test!(b, b"\x00\x00\x00\x00\x03\x00\x00\x00\x66\x6f\x6f\x00");
 }
 
+#[test]
+fn string_error() {
+    let mut encoder = Encoder::new();
+    let v = Value::String(String::from("f\0o"));
+    assert_eq!(
+        encoder.value(&v, true),
+        Err(EncodeError::StringNul(String::from("f\0o")))
+    );
+}
+
 #[test]
 fn path_1() {
     let b = init_test!(b"", Value::ObjectPath("/test".try_into().unwrap()), true);
@@ -234,41 +256,48 @@ fn signature() {
     end_test!(b, b"\x01\x69\x00");
 }
 
+#[test]
+fn signature_maybe_type_error() {
+    let mut encoder = Encoder::new();
+    let v = Value::Signature(vec![Type::Maybe(Box::new(Type::Int32))]);
+    assert_eq!(encoder.value(&v, true), Err(EncodeError::MaybeType));
+}
+
 #[cfg(target_family = "unix")]
 #[test]
 fn unix_fd_1() {
     let mut encoder = Encoder::new();
-    let v = Value::UnixFD(1);
+    let v = Value::UnixFD(open_null());
     encoder.value(&v, true).unwrap();
     let b = encoder.buf;
     let fds = encoder.fds;
     end_test!(b, b"\x00\x00\x00\x00");
-    assert_eq!(&fds[..], &[1,][..]);
+    assert_eq!(&fds[..], &[raw_fd(&v)][..]);
 }
 
 #[cfg(target_family = "unix")]
 #[test]
 fn unix_fd_2() {
     let mut encoder = Encoder::new();
-    let v = Value::UnixFD(1);
+    let v = Value::UnixFD(open_null());
     encoder.value(&v, true).unwrap();
     encoder.value(&v, true).unwrap();
     let b = encoder.buf;
     let fds = encoder.fds;
     end_test!(b, b"\x00\x00\x00\x00\x00\x00\x00\x00");
-    assert_eq!(&fds[..], &[1,][..]);
+    assert_eq!(&fds[..], &[raw_fd(&v)][..]);
 }
 
 #[cfg(target_family = "unix")]
 #[test]
 fn unix_fd_3() {
     let mut encoder = Encoder::new();
-    let v = Value::UnixFD(1);
-    encoder.value(&v, true).unwrap();
-    let v = Value::UnixFD(2);
-    encoder.value(&v, true).unwrap();
+    let v_1 = Value::UnixFD(open_null());
+    encoder.value(&v_1, true).unwrap();
+    let v_2 = v_1.clone();
+    encoder.value(&v_2, true).unwrap();
     let b = encoder.buf;
     let fds = encoder.fds;
     end_test!(b, b"\x00\x00\x00\x00\x01\x00\x00\x00");
-    assert_eq!(&fds[..], &[1, 2][..]);
+    assert_eq!(&fds[..], &[raw_fd(&v_1), raw_fd(&v_2)][..]);
 }
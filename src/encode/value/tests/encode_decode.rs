@@ -1,16 +1,20 @@
-use crate::decode::Decoder;
+use crate::decode::{DecodePathElement, Decoder};
 use crate::encode::Encoder;
 use crate::value::{Array, Type, Value};
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 
 fn encode_decode(value_1: &Value, is_le: bool) {
     let mut encoder = Encoder::new();
     encoder.value(value_1, is_le).unwrap();
 
     let bytes = encoder.buf.freeze();
-    let mut decoder = Decoder::new(bytes);
+    assert_eq!(value_1.encoded_len(0), bytes.len());
+    let mut decoder = Decoder::new(&bytes);
     let type_ = value_1.get_type().unwrap();
-    let value_2 = decoder.values(is_le, 0, &[type_]).unwrap();
+    let value_2 = decoder
+        .values(is_le, 0, 0, &[type_], DecodePathElement::BodyArgument)
+        .unwrap();
 
     if let Some(ordering) = value_1.partial_cmp(&value_2[0]) {
         match ordering {
@@ -27,10 +31,10 @@ fn encode_decode(value_1: &Value, is_le: bool) {
 
 #[test]
 fn array() {
-    let array_type = Type::from_string_to_signature("{s(bgav)}")
-        .unwrap()
-        .pop()
-        .unwrap();
+    let array_type = match Type::try_from("a{s(bgav)}").unwrap() {
+        Type::Array(array_type) => *array_type,
+        type_ => panic!("unexpected type: {}", type_),
+    };
     let array = Array::new(Vec::new(), array_type).unwrap();
     let non_empty_variant = Value::Variant(Box::new(Value::Array(array)));
     let variant_signature = Type::Variant;
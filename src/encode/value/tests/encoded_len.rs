@@ -0,0 +1,57 @@
+use crate::encode::Encoder;
+#[cfg(target_family = "unix")]
+use crate::value::open_null;
+use crate::value::{Array, Type, Value};
+use std::convert::TryInto;
+
+fn encoded_len(value: &Value) {
+    for offset in 0..8 {
+        let mut encoder = Encoder::new();
+        encoder.buf.extend_from_slice(&[0; 8][..offset]);
+        encoder.value(value, true).unwrap();
+        assert_eq!(value.encoded_len(offset), encoder.buf.len() - offset);
+    }
+}
+
+#[test]
+fn basic_types() {
+    encoded_len(&Value::Byte(1));
+    encoded_len(&Value::Boolean(true));
+    encoded_len(&Value::Int16(1));
+    encoded_len(&Value::Uint16(1));
+    encoded_len(&Value::Int32(1));
+    encoded_len(&Value::Uint32(1));
+    encoded_len(&Value::Int64(1));
+    encoded_len(&Value::Uint64(1));
+    encoded_len(&Value::Double(1.0));
+    encoded_len(&Value::String("Hello".to_string()));
+    encoded_len(&Value::ObjectPath("/object/path".try_into().unwrap()));
+    encoded_len(&Value::Signature(
+        Type::from_string_to_signature("a{s(bgav)}y").unwrap(),
+    ));
+    #[cfg(target_family = "unix")]
+    encoded_len(&Value::UnixFD(open_null()));
+}
+
+#[test]
+fn containers() {
+    let array = Array::new(Vec::new(), Type::Uint64).unwrap();
+    encoded_len(&Value::Array(array));
+
+    let array = Array::new(vec![Value::Uint64(1), Value::Uint64(2)], Type::Uint64).unwrap();
+    encoded_len(&Value::Array(array));
+
+    let struct_ = vec![Value::Byte(1), Value::Double(1.0), Value::Uint16(2)];
+    let struct_ = Value::Struct(struct_.try_into().unwrap());
+    encoded_len(&struct_);
+
+    let dict_entry = Value::DictEntry(Box::new((
+        Value::String("key".to_string()),
+        Value::Variant(Box::new(struct_)),
+    )));
+    encoded_len(&dict_entry);
+
+    let dict_entry_type = Type::DictEntry(Box::new((Type::String, Type::Variant)));
+    let array = Array::new(vec![dict_entry.clone(), dict_entry], dict_entry_type).unwrap();
+    encoded_len(&Value::Array(array));
+}
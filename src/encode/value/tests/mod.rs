@@ -1,3 +1,4 @@
 mod basic_type;
 mod container;
 mod encode_decode;
+mod encoded_len;
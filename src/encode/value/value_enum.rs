@@ -1,5 +1,7 @@
-use crate::encode::{EncodeResult, Encoder};
+use crate::encode::{EncodeError, EncodeResult, Encoder};
 use crate::value::Value;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::AsFd;
 
 impl Encoder {
     /// Encode a `Value` object to a byte array.
@@ -45,18 +47,20 @@ impl Encoder {
                 self.object_path(s, is_le);
                 Ok(())
             }
-            Value::String(s) => {
-                self.string(s, is_le);
-                Ok(())
-            }
+            Value::String(s) => self.string(s, is_le),
             Value::Signature(s) => self.signature(s),
             Value::Array(array) => self.array(array, is_le),
             Value::Struct(struct_) => self.encode_struct(struct_, is_le),
             Value::DictEntry(b) => self.dict_entry(b, is_le),
             Value::Variant(v) => self.variant(v, is_le),
+            Value::Maybe(_) => Err(EncodeError::MaybeType),
             #[cfg(target_family = "unix")]
             Value::UnixFD(fd) => {
-                self.unix_fd(*fd, is_le);
+                self.unix_fd(fd.as_fd(), is_le);
+                Ok(())
+            }
+            Value::UnixFdIndex(i) => {
+                self.uint_32(*i, is_le);
                 Ok(())
             }
         }
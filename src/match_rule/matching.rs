@@ -21,6 +21,7 @@ fn get_str_from_string_or_object_path(value: &Value) -> Option<&str> {
 }
 
 impl MatchRule {
+    #[allow(clippy::get_first)]
     pub fn matching_rule(&self, message: &Message) -> bool {
         match self {
             MatchRule::Type(r#type) => r#type == &message.get_type(),
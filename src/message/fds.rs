@@ -0,0 +1,201 @@
+use crate::message::Message;
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+use crate::value::Value;
+#[cfg(target_family = "unix")]
+use std::io::ErrorKind;
+#[cfg(target_family = "unix")]
+use std::mem::replace;
+#[cfg(target_family = "unix")]
+use thiserror::Error;
+
+/// An enum representing all errors, which can occur during attaching FDs to a [`Message`].
+#[cfg(target_family = "unix")]
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum FdsError {
+    #[error("Index of a FD is not smaller than the number of FDs: {0} >= {1}")]
+    UnixFdIndex(u32, usize),
+    #[error("Could not duplicate the FD: {0}")]
+    DuplicateFd(ErrorKind),
+}
+
+impl Message {
+    /// Returns all FDs, which are attached to the body, in the order, in which they are encoded.
+    #[cfg(target_family = "unix")]
+    pub fn get_body_fds(&self) -> Vec<&UnixFd> {
+        let mut fds = Vec::new();
+        for value in &self.body {
+            value.for_each_unix_fd(&mut |v| {
+                if let Value::UnixFD(fd) = v {
+                    fds.push(fd);
+                }
+            });
+        }
+        fds
+    }
+
+    /// Returns the indices of all FDs, which are referenced by a [`Value::UnixFdIndex`] in the
+    /// body, in the order, in which they are encoded.
+    ///
+    /// [`Value::UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    pub fn get_body_fd_indices(&self) -> Vec<u32> {
+        let mut indices = Vec::new();
+        for value in &self.body {
+            value.for_each_unix_fd(&mut |v| {
+                if let Value::UnixFdIndex(i) = v {
+                    indices.push(*i);
+                }
+            });
+        }
+        indices
+    }
+
+    /// Re-index the body for a new FD array, which contains `unix_fds` FDs. The index of every
+    /// [`Value::UnixFdIndex`] in the body is replaced by the result of `f` and the [`unix_fds`]
+    /// header field is set to `unix_fds`.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::message::Message;
+    /// # use dbus_message_parser::value::Value;
+    /// # use std::convert::TryInto;
+    /// let mut msg = Message::signal(
+    ///     "/object/path".try_into().unwrap(),
+    ///     "interface.name".try_into().unwrap(),
+    ///     "SignalName".try_into().unwrap(),
+    /// );
+    /// msg.add_value(Value::UnixFdIndex(0));
+    /// msg.add_value(Value::UnixFdIndex(1));
+    ///
+    /// // The FDs of the message are appended to an FD array, which already contains 3 FDs.
+    /// msg.remap_unix_fd_indices(5, |i| i + 3);
+    /// assert_eq!(msg.get_body_fd_indices(), vec![3, 4]);
+    /// assert_eq!(msg.get_unix_fds(), Some(5));
+    /// ```
+    ///
+    /// [`Value::UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    /// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
+    pub fn remap_unix_fd_indices<F>(&mut self, unix_fds: u32, mut f: F)
+    where
+        F: FnMut(u32) -> u32,
+    {
+        for value in &mut self.body {
+            value.remap_unix_fd_indices(&mut f);
+        }
+        self.header.fields.unix_fds = if unix_fds == 0 { None } else { Some(unix_fds) };
+    }
+
+    /// Replace every [`Value::UnixFdIndex`] in the body by a [`Value::UnixFD`], which is a
+    /// duplicate of the FD at the index in `fds`. The [`unix_fds`] header field is set to the
+    /// number of attached FDs.
+    ///
+    /// If an index is not smaller than the number of `fds` or a FD could not be duplicated, then
+    /// an error is returned and the message is not changed.
+    ///
+    /// [`Value::UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    /// [`Value::UnixFD`]: crate::value::Value::UnixFD
+    /// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
+    #[cfg(target_family = "unix")]
+    pub fn attach_fds(&mut self, fds: &[UnixFd]) -> Result<(), FdsError> {
+        let mut attached = Vec::new();
+        for i in self.get_body_fd_indices() {
+            let fd = fds
+                .get(i as usize)
+                .ok_or(FdsError::UnixFdIndex(i, fds.len()))?
+                .try_clone()
+                .map_err(|e| FdsError::DuplicateFd(e.kind()))?;
+            attached.push(fd);
+        }
+
+        // Every FD is duplicated, so the body can be changed.
+        let mut attached = attached.into_iter();
+        for value in &mut self.body {
+            value.for_each_unix_fd_mut(&mut |v| {
+                if let Value::UnixFdIndex(_) = v {
+                    if let Some(fd) = attached.next() {
+                        *v = Value::UnixFD(fd);
+                    }
+                }
+            });
+        }
+
+        let unix_fds = self.get_body_fds().len() as u32;
+        self.header.fields.unix_fds = if unix_fds == 0 { None } else { Some(unix_fds) };
+        Ok(())
+    }
+
+    /// Take all FDs, which are attached to the body, and replace them by a
+    /// [`Value::UnixFdIndex`], which references the FD in the returned FD array. This is the FD
+    /// array, which has to be sent with the encoded message. The [`unix_fds`] header field is set
+    /// accordingly.
+    ///
+    /// If the body already contains a [`Value::UnixFdIndex`], then the returned FDs have to be
+    /// appended to the FDs, which are declared by the [`unix_fds`] header field, and the indices
+    /// start after them.
+    ///
+    /// [`Value::UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    /// [`unix_fds`]: crate::message::MessageHeaderFields::unix_fds
+    #[cfg(target_family = "unix")]
+    pub fn detach_fds(&mut self) -> Vec<UnixFd> {
+        let offset = if self.get_body_fd_indices().is_empty() {
+            0
+        } else {
+            self.header.fields.unix_fds.unwrap_or(0)
+        };
+
+        let mut fds = Vec::new();
+        for value in &mut self.body {
+            value.for_each_unix_fd_mut(&mut |v| {
+                if let Value::UnixFD(_) = v {
+                    let i = offset + fds.len() as u32;
+                    if let Value::UnixFD(fd) = replace(v, Value::UnixFdIndex(i)) {
+                        fds.push(fd);
+                    }
+                }
+            });
+        }
+
+        if !fds.is_empty() {
+            self.header.fields.unix_fds = Some(offset + fds.len() as u32);
+        }
+        fds
+    }
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn attach_detach_fds() {
+    use crate::value::{open_null, Struct};
+    use std::convert::TryInto;
+    use std::os::unix::io::AsRawFd;
+
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    let struct_ = Struct(vec![Value::UnixFdIndex(1), Value::UnixFdIndex(0)]);
+    msg.add_value(Value::Struct(struct_));
+    msg.add_value(Value::UnixFdIndex(1));
+
+    let fds = [open_null()];
+    assert_eq!(msg.attach_fds(&fds), Err(FdsError::UnixFdIndex(1, 1)));
+    assert_eq!(msg.get_body_fd_indices(), vec![1, 0, 1]);
+
+    let fds = [open_null(), open_null()];
+    assert_eq!(msg.attach_fds(&fds), Ok(()));
+    assert!(msg.get_body_fd_indices().is_empty());
+    assert_eq!(msg.get_unix_fds(), Some(3));
+    let body_fds = msg.get_body_fds();
+    assert_eq!(body_fds.len(), 3);
+    for fd in body_fds {
+        assert!(fds.iter().all(|f| f.as_raw_fd() != fd.as_raw_fd()));
+    }
+
+    let detached = msg.detach_fds();
+    assert_eq!(detached.len(), 3);
+    assert!(msg.get_body_fds().is_empty());
+    assert_eq!(msg.get_body_fd_indices(), vec![0, 1, 2]);
+    assert_eq!(msg.get_unix_fds(), Some(3));
+    assert_eq!(msg.validate(), Ok(()));
+}
@@ -70,7 +70,6 @@ pub enum FieldsError {
     Sender(Value),
     #[error("Variant does not contain a Signature: {0:?}")]
     Signature(Value),
-    #[cfg(target_family = "unix")]
     #[error("Variant does not contain a Uint32: {0:?}")]
     UnixFDs(Value),
     #[error("The byte does not has a valid number: {0}")]
@@ -91,15 +90,27 @@ pub enum FieldsError {
     MultipleSender(Value),
     #[error("The signature is defined mutlple times: {0:?}")]
     MultipleSignature(Value),
-    #[cfg(target_family = "unix")]
     #[error("The unix fds is defined mutlple times: {0:?}")]
     MultipleUnixFDs(Value),
 }
 
+/// Returns `true` if `code` can be the code of an unknown header field. The code 0 is invalid and
+/// the codes 1 to 9 are the codes of the known header fields.
+#[inline]
+pub(crate) const fn is_unknown_code(code: u8) -> bool {
+    9 < code
+}
+
 /// An struct representing the [header fields].
 ///
+/// Header fields with an unknown code are kept in [`unknown`], so they can be forwarded unchanged.
+///
+/// The struct does not implement `Eq` and `Ord`, because the values of the unknown header fields
+/// can contain a [`Value::Double`].
+///
 /// [header fields]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-header-fields
-#[derive(Debug, Clone, PartialOrd, PartialEq, Ord, Eq, Default)]
+/// [`unknown`]: Fields::unknown
+#[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
 pub struct Fields {
     pub path: Option<ObjectPath>,
     pub interface: Option<Interface>,
@@ -109,8 +120,10 @@ pub struct Fields {
     pub destination: Option<Bus>,
     pub sender: Option<Bus>,
     pub signature: Option<Vec<Type>>,
-    #[cfg(target_family = "unix")]
     pub unix_fds: Option<u32>,
+    /// The header fields with an unknown code and their values. The code must not be 0 and must
+    /// not be the code of a known header field, otherwise the header fields cannot be encoded.
+    pub unknown: Vec<(u8, Value)>,
 }
 
 impl Fields {
@@ -169,7 +182,6 @@ impl Fields {
         MultipleSignature,
         Signature
     );
-    #[cfg(target_family = "unix")]
     try_set_field!(try_set_unix_fds, unix_fds, Uint32, MultipleUnixFDs, UnixFDs);
 
     fn try_set_field(&mut self, b: u8, v: Value) -> Result<(), FieldsError> {
@@ -182,10 +194,14 @@ impl Fields {
             6 => self.try_set_destination(v),
             7 => self.try_set_sender(v),
             8 => self.try_set_signature(v),
-            #[cfg(target_family = "unix")]
             9 => self.try_set_unix_fds(v),
             // Invalid number.
-            b => Err(FieldsError::InvalidNumber(b)),
+            0 => Err(FieldsError::InvalidNumber(0)),
+            // Unknown fields have to be ignored.
+            b => {
+                self.unknown.push((b, v));
+                Ok(())
+            }
         }
     }
 }
@@ -242,8 +258,13 @@ impl From<Fields> for Vec<Value> {
         add_to_vec!(values, fields, destination, 6, String, to_string);
         add_to_vec!(values, fields, sender, 7, String, to_string);
         add_to_vec!(values, fields, signature, 8, Signature);
-        #[cfg(target_family = "unix")]
         add_to_vec!(values, fields, unix_fds, 9, Uint32);
+        for (b, v) in fields.unknown {
+            values.push(Value::Struct(Struct(vec![
+                Value::Byte(b),
+                Value::Variant(Box::new(v)),
+            ])));
+        }
         values
     }
 }
@@ -1,6 +1,6 @@
 use super::{HeaderError, HeaderFields};
 use crate::{
-    message::{Message, MessageFlags, MessageType},
+    message::{Message, MessageFlags, MessageType, MAJOR_PROTOCOL_VERSION},
     value::{Bus, Error, Interface, Member, ObjectPath, Type, Value},
 };
 use std::convert::TryInto;
@@ -25,56 +25,76 @@ macro_rules! has_field {
     };
 }
 
-#[inline]
-fn check_header_fields(
+/// Returns the errors of all required [header fields], which are missing for the message type.
+///
+/// [header fields]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-header-fields
+pub(crate) fn missing_header_fields(
     message_type: MessageType,
     fields: &HeaderFields,
-) -> Result<(), HeaderError> {
+) -> Vec<HeaderError> {
+    let mut errors = Vec::new();
     match message_type {
         MessageType::MethodCall => {
             if fields.path.is_none() {
-                return Err(HeaderError::MissingPath);
+                errors.push(HeaderError::MissingPath);
             }
 
             if fields.member.is_none() {
-                return Err(HeaderError::MissingMember);
+                errors.push(HeaderError::MissingMember);
             }
         }
         MessageType::Signal => {
             if fields.path.is_none() {
-                return Err(HeaderError::MissingPath);
+                errors.push(HeaderError::MissingPath);
             }
 
             if fields.interface.is_none() {
-                return Err(HeaderError::MissingInterface);
+                errors.push(HeaderError::MissingInterface);
             }
 
             if fields.member.is_none() {
-                return Err(HeaderError::MissingMember);
+                errors.push(HeaderError::MissingMember);
             }
         }
         MessageType::Error => {
             if fields.error_name.is_none() {
-                return Err(HeaderError::MissingErrorName);
+                errors.push(HeaderError::MissingErrorName);
             }
 
             if fields.reply_serial.is_none() {
-                return Err(HeaderError::MissingReplySerial);
+                errors.push(HeaderError::MissingReplySerial);
             }
         }
         MessageType::MethodReturn => {
             if fields.reply_serial.is_none() {
-                return Err(HeaderError::MissingReplySerial);
+                errors.push(HeaderError::MissingReplySerial);
             }
         }
     }
-    Ok(())
+    errors
+}
+
+#[inline]
+fn check_header_fields(
+    message_type: MessageType,
+    fields: &HeaderFields,
+) -> Result<(), HeaderError> {
+    match missing_header_fields(message_type, fields)
+        .into_iter()
+        .next()
+    {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
 }
 
 /// This represents a DBus [message header].
 ///
+/// The struct does not implement `Eq` and `Ord`, because the [`HeaderFields`] do not implement
+/// them.
+///
 /// [message header]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol
-#[derive(Debug, Clone, PartialOrd, PartialEq, Ord, Eq)]
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub struct Header {
     pub(crate) is_le: bool,
     pub(crate) message_type: MessageType,
@@ -111,6 +131,18 @@ impl Header {
         Ok(header)
     }
 
+    /// It is true if the message is encoded in little endian.
+    #[inline]
+    pub const fn is_le(&self) -> bool {
+        self.is_le
+    }
+
+    /// Get the major protocol version.
+    #[inline]
+    pub const fn get_version(&self) -> u8 {
+        self.version
+    }
+
     /// Get the serial number.
     #[inline]
     pub const fn get_serial(&self) -> u32 {
@@ -258,7 +290,6 @@ impl Header {
         signature
     );
 
-    #[cfg(target_family = "unix")]
     get_field!(
         /// Get the [`unix_fds`], if there is one in the header field.
         ///
@@ -268,7 +299,6 @@ impl Header {
         u32
     );
 
-    #[cfg(target_family = "unix")]
     has_field!(
         /// It is true if the message contains a [`unix_fds`] in the header fields.
         ///
@@ -277,11 +307,20 @@ impl Header {
         unix_fds
     );
 
+    /// Get the header fields with an unknown code, see [`unknown`].
+    ///
+    /// [`unknown`]: crate::message::MessageHeaderFields::unknown
+    #[inline]
+    pub fn get_unknown_fields(&self) -> &[(u8, Value)] {
+        &self.fields.unknown
+    }
+
     /// Create a message return from this [`Message`].
     /// Only works if this [`Message`] is a [`MethodCall`].
     ///
     /// [`Message`]: crate::message::Message
     /// [`MethodCall`]: crate::message::MessageType::MethodCall
+    #[allow(clippy::result_large_err)]
     pub fn method_return(&self) -> Result<Message, Message> {
         if let MessageType::MethodCall = self.message_type {
             let message_type = MessageType::MethodReturn;
@@ -304,7 +343,7 @@ impl Header {
                 is_le: self.is_le,
                 message_type,
                 message_flags,
-                version: 1,
+                version: MAJOR_PROTOCOL_VERSION,
                 serial: 0,
                 fields,
             };
@@ -411,7 +450,7 @@ impl Header {
             is_le: self.is_le,
             message_type,
             message_flags,
-            version: 1,
+            version: MAJOR_PROTOCOL_VERSION,
             serial: 0,
             fields,
         };
@@ -3,5 +3,7 @@ mod fields;
 mod header_struct;
 
 pub use error::Error as HeaderError;
+pub(crate) use fields::is_unknown_code;
 pub use fields::{Fields as HeaderFields, FieldsError as HeaderFieldsError};
+pub(crate) use header_struct::missing_header_fields;
 pub use header_struct::Header;
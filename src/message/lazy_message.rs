@@ -0,0 +1,94 @@
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+use crate::{
+    decode::Decoder,
+    message::{MessageHeader, MessageType},
+    value::Type,
+};
+use bytes::Bytes;
+use std::iter::Enumerate;
+use std::slice::Iter;
+
+/// This represents a DBus [message], where only the header is decoded.
+///
+/// The body is kept as raw bytes and is only decoded on demand, see [`decode_body`],
+/// [`decode_body_ref`] and [`args`]. If the message is encoded again, the raw bytes of the body are
+/// appended without decoding them.
+///
+/// A [`LazyMessage`], which is decoded with FDs, owns duplicates of them. Cloning it duplicates
+/// the FDs again and panics if this fails, and comparing two [`LazyMessage`]s compares the FD
+/// numbers, see [`Value`].
+///
+/// [message]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol
+/// [`decode_body`]: crate::message::LazyMessage::decode_body
+/// [`decode_body_ref`]: crate::message::LazyMessage::decode_body_ref
+/// [`args`]: crate::message::LazyMessage::args
+/// [`Value`]: crate::value::Value#attached-fds
+#[derive(Debug, Clone, PartialEq)]
+pub struct LazyMessage {
+    pub(crate) header: MessageHeader,
+    pub(crate) signature: Vec<Type>,
+    pub(crate) body: Bytes,
+    #[cfg(target_family = "unix")]
+    pub(crate) fds: Vec<UnixFd>,
+}
+
+impl LazyMessage {
+    /// Get the header.
+    #[inline]
+    pub fn get_header(&self) -> &MessageHeader {
+        &self.header
+    }
+
+    /// Get the serial number.
+    #[inline]
+    pub const fn get_serial(&self) -> u32 {
+        self.header.get_serial()
+    }
+
+    /// Set the serial number.
+    #[inline]
+    pub fn set_serial(&mut self, serial: u32) {
+        self.header.serial = serial;
+    }
+
+    /// Get the message type.
+    #[inline]
+    pub fn get_type(&self) -> MessageType {
+        self.header.get_type()
+    }
+
+    /// Get the signature of the body.
+    #[inline]
+    pub fn get_signature(&self) -> &[Type] {
+        &self.signature
+    }
+
+    /// Get the raw bytes of the body.
+    #[inline]
+    pub fn get_body_bytes(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Get the FDs, which belong to this message. The FDs are closed, when the message is
+    /// dropped.
+    #[cfg(target_family = "unix")]
+    #[inline]
+    pub fn get_fds(&self) -> &[UnixFd] {
+        &self.fds
+    }
+}
+
+/// An iterator over the arguments of the body of a [`LazyMessage`], which decodes one argument per
+/// step. See [`LazyMessage::args`].
+///
+/// After an error occurs, the iterator returns `None`.
+///
+/// [`LazyMessage`]: crate::message::LazyMessage
+/// [`LazyMessage::args`]: crate::message::LazyMessage::args
+pub struct LazyMessageArgs<'a> {
+    pub(crate) decoder: Decoder<'a>,
+    pub(crate) is_le: bool,
+    pub(crate) signature: Enumerate<Iter<'a, Type>>,
+    pub(crate) finished: bool,
+}
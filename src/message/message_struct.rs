@@ -1,7 +1,7 @@
 use crate::{
     message::{
         header::{Header, HeaderFields},
-        MessageFlags, MessageType,
+        MessageFlags, MessageType, MAJOR_PROTOCOL_VERSION,
     },
     value::{Bus, Error, Interface, Member, ObjectPath, Type, TypeError, Value},
 };
@@ -29,7 +29,14 @@ macro_rules! has_field {
 
 /// This represents a DBus [message].
 ///
+/// The body can contain attached FDs. Like for a [`Value`], cloning a [`Message`] with attached
+/// FDs duplicates them and panics if this fails, and comparing two [`Message`]s compares the FD
+/// numbers. Use [`detach_fds`] to replace the attached FDs by their indices before cloning or
+/// comparing the [`Message`].
+///
 /// [message]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol
+/// [`Value`]: crate::value::Value#attached-fds
+/// [`detach_fds`]: crate::message::Message::detach_fds
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub struct Message {
     pub(crate) header: Header,
@@ -63,7 +70,7 @@ impl Message {
             is_le: true,
             message_type: MessageType::MethodCall,
             message_flags: MessageFlags::empty(),
-            version: 1,
+            version: MAJOR_PROTOCOL_VERSION,
             serial: 0,
             fields,
         };
@@ -88,7 +95,7 @@ impl Message {
             is_le: true,
             message_type: MessageType::Signal,
             message_flags: MessageFlags::NO_REPLY_EXPECTED,
-            version: 1,
+            version: MAJOR_PROTOCOL_VERSION,
             serial: 0,
             fields,
         };
@@ -158,6 +165,31 @@ impl Message {
         msg
     }
 
+    /// It is true if the message is encoded in little endian.
+    #[inline]
+    pub const fn is_le(&self) -> bool {
+        self.header.is_le()
+    }
+
+    /// Set the endianness, which is used to encode the message. Little endian is used by
+    /// default.
+    #[inline]
+    pub fn set_is_le(&mut self, is_le: bool) {
+        self.header.is_le = is_le;
+    }
+
+    /// Get the major protocol version.
+    #[inline]
+    pub const fn get_version(&self) -> u8 {
+        self.header.get_version()
+    }
+
+    /// Set the major protocol version, which selects the format to encode the message.
+    #[inline]
+    pub fn set_version(&mut self, version: u8) {
+        self.header.version = version;
+    }
+
     /// Get the serial number.
     #[inline]
     pub const fn get_serial(&self) -> u32 {
@@ -295,7 +327,6 @@ impl Message {
         has_signature
     );
 
-    #[cfg(target_family = "unix")]
     get_field!(
         /// Get the [`unix_fds`], if there is one in the header field.
         ///
@@ -304,7 +335,6 @@ impl Message {
         u32
     );
 
-    #[cfg(target_family = "unix")]
     has_field!(
         /// It is true if the message contains a [`unix_fds`] in the header fields.
         ///
@@ -312,6 +342,14 @@ impl Message {
         has_unix_fds
     );
 
+    /// Get the header fields with an unknown code, see [`unknown`].
+    ///
+    /// [`unknown`]: crate::message::MessageHeaderFields::unknown
+    #[inline]
+    pub fn get_unknown_fields(&self) -> &[(u8, Value)] {
+        self.header.get_unknown_fields()
+    }
+
     /// Add a new value to the body.
     pub fn add_value(&mut self, value: Value) {
         self.body.push(value);
@@ -321,6 +359,7 @@ impl Message {
     /// Only works if this [`Message`] is a [`MethodCall`].
     ///
     /// [`MethodCall`]: crate::message::MessageType::MethodCall
+    #[allow(clippy::result_large_err)]
     pub fn method_return(&self) -> Result<Message, Message> {
         self.header.method_return()
     }
@@ -1,15 +1,35 @@
+mod fds;
 mod flags;
 mod header;
+mod lazy_message;
 mod message_struct;
 mod types;
+mod validation;
 
+#[cfg(target_family = "unix")]
+pub use fds::FdsError;
 pub use flags::MessageFlags;
+pub(crate) use header::is_unknown_code;
 pub use header::{
     Header as MessageHeader, HeaderError as MessageHeaderError,
     HeaderFields as MessageHeaderFields, HeaderFieldsError as MessageHeaderFieldsError,
 };
+pub use lazy_message::{LazyMessage, LazyMessageArgs};
 pub use message_struct::Message;
 pub use types::MessageType;
+pub use validation::ValidationError;
+
+/// The major protocol version, which can be decoded and encoded.
+pub const MAJOR_PROTOCOL_VERSION: u8 = 1;
+
+/// The major protocol version of messages in the [GVariant format].
+///
+/// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+pub const GVARIANT_PROTOCOL_VERSION: u8 = 2;
 
 /// The maximum length of a message. 128 MiB
 pub const MAXIMUM_MESSAGE_LENGTH: usize = 134217728;
+
+/// The length of the fixed part of the message header including the length of the header fields
+/// array. These bytes are enough to determine the length of the whole message.
+pub const MINIMUM_HEADER_LENGTH: usize = 16;
@@ -0,0 +1,324 @@
+use crate::{
+    encode::algin,
+    message::{
+        header::missing_header_fields, is_unknown_code, Message, MessageHeaderError,
+        MAJOR_PROTOCOL_VERSION, MAXIMUM_MESSAGE_LENGTH,
+    },
+    value::{
+        Bus, BusError, Error, ErrorError, Interface, InterfaceError, Member, MemberError,
+        ObjectPath, ObjectPathError, Type, TypeError, Value, MAXIMUM_ARRAY_LENGTH,
+    },
+};
+#[cfg(test)]
+use crate::{
+    message::{MessageFlags, MessageHeader, MessageHeaderFields, MessageType},
+    value::Array,
+};
+use std::convert::TryFrom;
+use thiserror::Error as ThisError;
+
+/// An enum representing all violations of the [DBus specification], which are reported by
+/// [`Message::validate`].
+///
+/// [DBus specification]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol
+#[derive(Debug, PartialEq, ThisError)]
+pub enum ValidationError {
+    #[error("Major protocol version is not supported: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Header field is missing: {0}")]
+    MissingHeaderField(#[from] MessageHeaderError),
+    #[error("Code of an unknown header field is invalid or of a known header field: {0}")]
+    HeaderFieldCode(u8),
+    #[error("Bus is invalid: {0}")]
+    Bus(#[from] BusError),
+    #[error("ObjectPath is invalid: {0}")]
+    ObjectPath(#[from] ObjectPathError),
+    #[error("Interface is invalid: {0}")]
+    Interface(#[from] InterfaceError),
+    #[error("Member is invalid: {0}")]
+    Member(#[from] MemberError),
+    #[error("Error is invalid: {0}")]
+    ErrorName(#[from] ErrorError),
+    #[error("Body signature is invalid: {0}")]
+    BodySignature(TypeError),
+    #[error("Type of a value is invalid: {0}")]
+    Type(#[from] TypeError),
+    #[error("The type of an array element is different: expected '{0}' got '{1}'")]
+    ArrayElementType(Type, Type),
+    #[error("Array length is too big: {MAXIMUM_ARRAY_LENGTH} < {0}")]
+    ArrayTooBig(usize),
+    #[error("String contains a null byte: {0:?}")]
+    StringNul(String),
+    #[error("Maybe type is not supported by the D-Bus format")]
+    MaybeType,
+    #[error("The number of FDs in the header field is different: header {0} body {1}")]
+    UnixFds(u32, usize),
+    #[error("Index of a FD is not smaller than the number of FDs: {0} >= {1}")]
+    UnixFdIndex(u32, u32),
+    #[error("Body contains attached FDs and indices of FDs")]
+    MixedUnixFds,
+    #[error("Message is too long: {MAXIMUM_MESSAGE_LENGTH} < {0}")]
+    MessageTooLong(usize),
+}
+
+/// Check a name again, which is already checked, when the name is created.
+macro_rules! check_name {
+    ($violations:ident, $name_type:ident, $name:expr) => {
+        if let Err(e) = $name_type::try_from($name.as_ref() as &str) {
+            $violations.push(ValidationError::from(e));
+        }
+    };
+}
+
+/// Check, that the indices of all [`Value::UnixFdIndex`] in the `Value` are smaller than
+/// `unix_fds`.
+fn validate_unix_fd_indices(value: &Value, unix_fds: u32, violations: &mut Vec<ValidationError>) {
+    match value {
+        Value::UnixFdIndex(i) if unix_fds <= *i => {
+            violations.push(ValidationError::UnixFdIndex(*i, unix_fds));
+        }
+        Value::Array(array) => array
+            .as_ref()
+            .iter()
+            .for_each(|v| validate_unix_fd_indices(v, unix_fds, violations)),
+        Value::Struct(struct_) => struct_
+            .as_ref()
+            .iter()
+            .for_each(|v| validate_unix_fd_indices(v, unix_fds, violations)),
+        Value::DictEntry(b) => {
+            validate_unix_fd_indices(&b.0, unix_fds, violations);
+            validate_unix_fd_indices(&b.1, unix_fds, violations);
+        }
+        Value::Variant(v) => validate_unix_fd_indices(v, unix_fds, violations),
+        Value::Maybe(maybe) => {
+            if let Some(v) = maybe.get_value() {
+                validate_unix_fd_indices(v, unix_fds, violations);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check the `Value`, if it is encoded at `offset` and returns the offset, where the encoded
+/// `Value` ends.
+fn validate_value(value: &Value, offset: usize, violations: &mut Vec<ValidationError>) -> usize {
+    match value {
+        Value::String(s) => {
+            if s.contains('\0') {
+                violations.push(ValidationError::StringNul(s.clone()));
+            }
+            value.encoded_end(offset)
+        }
+        Value::ObjectPath(object_path) => {
+            check_name!(violations, ObjectPath, object_path);
+            value.encoded_end(offset)
+        }
+        Value::Signature(signature) => {
+            if let Err(e) = Type::from_signature_to_string(signature) {
+                violations.push(ValidationError::Type(e));
+            }
+            if signature.iter().any(Type::contains_maybe) {
+                violations.push(ValidationError::MaybeType);
+            }
+            value.encoded_end(offset)
+        }
+        Value::Array(array) => {
+            let type_ = array.get_type();
+            if let Err(e) = type_.check_dict_entries(true) {
+                violations.push(ValidationError::Type(e));
+            }
+
+            let start = algin(algin(offset, 4) + 4, type_.get_alignment());
+            let mut end = start;
+            for element in array.as_ref() {
+                match element.get_element_type() {
+                    Ok(element_type) => {
+                        if &element_type != type_ {
+                            violations.push(ValidationError::ArrayElementType(
+                                type_.clone(),
+                                element_type,
+                            ));
+                        }
+                    }
+                    Err(e) => violations.push(ValidationError::Type(e)),
+                }
+                end = validate_value(element, end, violations);
+            }
+
+            let array_len = end - start;
+            if MAXIMUM_ARRAY_LENGTH < array_len {
+                violations.push(ValidationError::ArrayTooBig(array_len));
+            }
+            end
+        }
+        Value::Struct(struct_) => struct_
+            .as_ref()
+            .iter()
+            .fold(algin(offset, 8), |offset, value| {
+                validate_value(value, offset, violations)
+            }),
+        Value::DictEntry(b) => {
+            let offset = validate_value(&b.0, algin(offset, 8), violations);
+            validate_value(&b.1, offset, violations)
+        }
+        Value::Variant(v) => {
+            if let Err(e) = v.get_type() {
+                violations.push(ValidationError::Type(e));
+            }
+            validate_value(v, offset + 1 + v.signature_len() + 1, violations)
+        }
+        Value::Maybe(_) => {
+            violations.push(ValidationError::MaybeType);
+            offset
+        }
+        _ => value.encoded_end(offset),
+    }
+}
+
+/// Returns the number of attached FDs in the `Value`.
+#[cfg(target_family = "unix")]
+fn count_unix_fds(value: &Value) -> usize {
+    match value {
+        Value::UnixFD(_) => 1,
+        Value::Array(array) => array.as_ref().iter().map(count_unix_fds).sum(),
+        Value::Struct(struct_) => struct_.as_ref().iter().map(count_unix_fds).sum(),
+        Value::DictEntry(b) => count_unix_fds(&b.0) + count_unix_fds(&b.1),
+        Value::Variant(v) => count_unix_fds(v),
+        Value::Maybe(maybe) => maybe.get_value().map_or(0, count_unix_fds),
+        _ => 0,
+    }
+}
+
+impl Message {
+    /// Check the [`Message`] against all rules, which are enforced by the encoder and by the
+    /// peers, and returns all violations instead of only the first one.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::message::{Message, ValidationError};
+    /// # use dbus_message_parser::value::Value;
+    /// # use std::convert::TryInto;
+    /// let mut msg = Message::signal(
+    ///     "/object/path".try_into().unwrap(),
+    ///     "interface.name".try_into().unwrap(),
+    ///     "SignalName".try_into().unwrap(),
+    /// );
+    /// assert_eq!(msg.validate(), Ok(()));
+    ///
+    /// msg.add_value(Value::String("a\0b".to_string()));
+    /// msg.set_version(2);
+    /// assert_eq!(
+    ///     msg.validate(),
+    ///     Err(vec![
+    ///         ValidationError::UnsupportedVersion(2),
+    ///         ValidationError::StringNul("a\0b".to_string()),
+    ///     ])
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut violations = Vec::new();
+        let header = &self.header;
+        let fields = &header.fields;
+
+        if header.version != MAJOR_PROTOCOL_VERSION {
+            violations.push(ValidationError::UnsupportedVersion(header.version));
+        }
+
+        for error in missing_header_fields(header.message_type, fields) {
+            violations.push(ValidationError::MissingHeaderField(error));
+        }
+
+        if let Some(path) = &fields.path {
+            check_name!(violations, ObjectPath, path);
+        }
+        if let Some(interface) = &fields.interface {
+            check_name!(violations, Interface, interface);
+        }
+        if let Some(member) = &fields.member {
+            check_name!(violations, Member, member);
+        }
+        if let Some(error_name) = &fields.error_name {
+            check_name!(violations, Error, error_name);
+        }
+        if let Some(destination) = &fields.destination {
+            check_name!(violations, Bus, destination);
+        }
+        if let Some(sender) = &fields.sender {
+            check_name!(violations, Bus, sender);
+        }
+        for (code, value) in &fields.unknown {
+            if !is_unknown_code(*code) {
+                violations.push(ValidationError::HeaderFieldCode(*code));
+            }
+            validate_value(value, 0, &mut violations);
+        }
+
+        let mut body_signature = String::new();
+        for value in &self.body {
+            if let Err(e) = value.to_signature_string(&mut body_signature, 0, 0, 0) {
+                violations.push(ValidationError::BodySignature(e));
+                break;
+            }
+        }
+
+        // The body starts at an 8-byte boundary, so the alignment of the values does not depend
+        // on the header.
+        self.body.iter().fold(0, |offset, value| {
+            validate_value(value, offset, &mut violations)
+        });
+
+        // If the body has attached FDs, then the number of FDs is taken from them, when the
+        // message is encoded. Therefore, the body cannot contain indices of other FDs.
+        let unix_fds = fields.unix_fds.unwrap_or(0);
+        #[cfg(target_family = "unix")]
+        {
+            let body_unix_fds: usize = self.body.iter().map(count_unix_fds).sum();
+            if body_unix_fds != 0 {
+                if !self.get_body_fd_indices().is_empty() {
+                    violations.push(ValidationError::MixedUnixFds);
+                } else if fields.unix_fds.is_some() && unix_fds as usize != body_unix_fds {
+                    violations.push(ValidationError::UnixFds(unix_fds, body_unix_fds));
+                }
+            }
+        }
+        for value in &self.body {
+            validate_unix_fd_indices(value, unix_fds, &mut violations);
+        }
+
+        let length = self.encoded_len();
+        if MAXIMUM_MESSAGE_LENGTH < length {
+            violations.push(ValidationError::MessageTooLong(length));
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[test]
+fn validate_header_fields_and_array() {
+    let header = MessageHeader {
+        is_le: true,
+        message_type: MessageType::Error,
+        message_flags: MessageFlags::empty(),
+        version: MAJOR_PROTOCOL_VERSION,
+        serial: 1,
+        fields: MessageHeaderFields::default(),
+    };
+    let array = Array {
+        type_: Type::Byte,
+        array: vec![Value::Byte(0), Value::Int32(1)],
+    };
+    let msg = Message::new(header, vec![Value::Array(array)]);
+    assert_eq!(
+        msg.validate(),
+        Err(vec![
+            ValidationError::MissingHeaderField(MessageHeaderError::MissingErrorName),
+            ValidationError::MissingHeaderField(MessageHeaderError::MissingReplySerial),
+            ValidationError::ArrayElementType(Type::Byte, Type::Int32),
+        ])
+    );
+}
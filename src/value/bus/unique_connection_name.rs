@@ -29,6 +29,7 @@ impl TryFrom<u8> for Input {
     }
 }
 
+#[allow(clippy::empty_docs)]
 enum State {
     /// The beginning of the first element.
     Start,
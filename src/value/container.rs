@@ -18,8 +18,9 @@ pub enum ArrayError {
 
 impl Array {
     pub fn new(array: Vec<Value>, type_: Type) -> Result<Array, ArrayError> {
+        type_.check_dict_entries(true)?;
         for v in &array {
-            let s = v.get_type()?;
+            let s = v.get_element_type()?;
             if s != type_ {
                 return Err(ArrayError::TypeMismatch(type_, s));
             }
@@ -78,3 +79,48 @@ impl From<Struct> for Vec<Value> {
         struct_.0
     }
 }
+
+/// This represents a maybe value of the [GVariant format], which is either nothing or just a
+/// value. It is not supported by the D-Bus format.
+///
+/// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Maybe {
+    pub(crate) type_: Type,
+    pub(crate) value: Option<Box<Value>>,
+}
+
+impl Maybe {
+    /// Create a [`Maybe`] object without a value.
+    pub fn nothing(type_: Type) -> Maybe {
+        Maybe { type_, value: None }
+    }
+
+    /// Create a [`Maybe`] object with a value.
+    pub fn just(value: Value) -> Result<Maybe, TypeError> {
+        let type_ = value.get_type()?;
+        let maybe = Maybe {
+            type_,
+            value: Some(Box::new(value)),
+        };
+        Ok(maybe)
+    }
+
+    /// Get the type of the value.
+    #[inline]
+    pub const fn get_type(&self) -> &Type {
+        &self.type_
+    }
+
+    /// Get the value, if there is one.
+    #[inline]
+    pub fn get_value(&self) -> Option<&Value> {
+        self.value.as_deref()
+    }
+}
+
+impl From<Maybe> for Option<Value> {
+    fn from(maybe: Maybe) -> Self {
+        maybe.value.map(|value| *value)
+    }
+}
@@ -0,0 +1,327 @@
+use crate::value::{Array, ObjectPath, Struct, Type, TypeError, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use thiserror::Error;
+
+/// An enum representing all errors, which can occur during the conversion of a [`Value`] into a
+/// Rust type.
+#[derive(Debug, PartialEq, Error)]
+pub enum ConvertError {
+    #[error("The type of the value is different: expected '{0}' got '{1}'")]
+    TypeMismatch(Type, Type),
+    #[error("Could not get type of the value: {0}")]
+    TypeError(#[from] TypeError),
+}
+
+/// Returns the error for a `value`, which does not have the `expected` type.
+fn type_mismatch(expected: Type, value: &Value) -> ConvertError {
+    match value.get_element_type() {
+        Ok(type_) => ConvertError::TypeMismatch(expected, type_),
+        Err(e) => ConvertError::TypeError(e),
+    }
+}
+
+/// A Rust type, which corresponds to a single [`Type`].
+pub trait DBusType {
+    /// Returns the [`Type`] of the [`Value`], which represents the Rust type.
+    fn dbus_type() -> Type;
+}
+
+/// A Rust type, which corresponds to a basic [`Type`]. Only these types can be the key of a dict.
+///
+/// # Example
+/// ```compile_fail
+/// # use dbus_message_parser::value::IntoValue;
+/// # use std::collections::HashMap;
+/// // The key of a dict cannot be an array.
+/// let mut dict = HashMap::new();
+/// dict.insert(vec![1u8], 2u8);
+/// dict.into_value();
+/// ```
+pub trait DBusBasicType: DBusType {}
+
+/// Convert a Rust value into a [`Value`].
+///
+/// # Example
+/// ```
+/// # use dbus_message_parser::value::{IntoValue, Type, Value};
+/// let value = vec![1u32, 2].into_value();
+/// assert_eq!(value.get_type(), Ok(Type::Array(Box::new(Type::Uint32))));
+/// ```
+pub trait IntoValue: DBusType {
+    fn into_value(self) -> Value;
+}
+
+/// Convert a [`Value`] into a Rust value. The [`Value`] has to be of the [`Type`] of the Rust
+/// type, otherwise a [`ConvertError::TypeMismatch`] is returned.
+///
+/// # Example
+/// ```
+/// # use dbus_message_parser::value::{ConvertError, IntoValue, TryFromValue, Type, Value};
+/// let value = ("text", 1i32).into_value();
+/// assert_eq!(
+///     <(String, i32)>::try_from_value(value.clone()),
+///     Ok(("text".to_string(), 1))
+/// );
+/// assert_eq!(
+///     u32::try_from_value(value),
+///     Err(ConvertError::TypeMismatch(
+///         Type::Uint32,
+///         Type::Struct(vec![Type::String, Type::Int32])
+///     ))
+/// );
+/// ```
+pub trait TryFromValue: DBusType + Sized {
+    fn try_from_value(value: Value) -> Result<Self, ConvertError>;
+}
+
+macro_rules! basic_type {
+    ($rust_type:ty, $type:ident) => {
+        impl DBusType for $rust_type {
+            fn dbus_type() -> Type {
+                Type::$type
+            }
+        }
+
+        impl DBusBasicType for $rust_type {}
+
+        impl IntoValue for $rust_type {
+            fn into_value(self) -> Value {
+                Value::$type(self)
+            }
+        }
+
+        impl TryFromValue for $rust_type {
+            fn try_from_value(value: Value) -> Result<Self, ConvertError> {
+                match value {
+                    Value::$type(v) => Ok(v),
+                    value => Err(type_mismatch(Type::$type, &value)),
+                }
+            }
+        }
+    };
+}
+
+basic_type!(u8, Byte);
+basic_type!(bool, Boolean);
+basic_type!(i16, Int16);
+basic_type!(u16, Uint16);
+basic_type!(i32, Int32);
+basic_type!(u32, Uint32);
+basic_type!(i64, Int64);
+basic_type!(u64, Uint64);
+basic_type!(f64, Double);
+basic_type!(String, String);
+basic_type!(ObjectPath, ObjectPath);
+
+impl DBusType for &str {
+    fn dbus_type() -> Type {
+        Type::String
+    }
+}
+
+impl DBusBasicType for &str {}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl<T: DBusType> DBusType for Vec<T> {
+    fn dbus_type() -> Type {
+        Type::Array(Box::new(T::dbus_type()))
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        // Every element has the type of `T` and the key of every dict is a basic type, therefore
+        // the array does not have to be checked by `Array::new`.
+        let array = self.into_iter().map(IntoValue::into_value).collect();
+        Value::Array(Array {
+            type_: T::dbus_type(),
+            array,
+        })
+    }
+}
+
+impl<T: TryFromValue> TryFromValue for Vec<T> {
+    fn try_from_value(value: Value) -> Result<Self, ConvertError> {
+        match value {
+            Value::Array(array) if array.type_ == T::dbus_type() => {
+                array.array.into_iter().map(T::try_from_value).collect()
+            }
+            value => Err(type_mismatch(Self::dbus_type(), &value)),
+        }
+    }
+}
+
+/// Returns the [`Type`] of the dict entries of a dict.
+fn dict_entry_type<K: DBusBasicType, V: DBusType>() -> Type {
+    Type::DictEntry(Box::new((K::dbus_type(), V::dbus_type())))
+}
+
+/// Convert the entries of a dict into an array of dict entries.
+fn dict_into_value<K, V, I>(entries: I) -> Value
+where
+    K: DBusBasicType + IntoValue,
+    V: IntoValue,
+    I: Iterator<Item = (K, V)>,
+{
+    let array = entries
+        .map(|(k, v)| Value::DictEntry(Box::new((k.into_value(), v.into_value()))))
+        .collect();
+    Value::Array(Array {
+        type_: dict_entry_type::<K, V>(),
+        array,
+    })
+}
+
+/// Convert an array of dict entries into the entries of a dict.
+fn dict_try_from_value<K, V, D>(value: Value) -> Result<D, ConvertError>
+where
+    K: DBusBasicType + TryFromValue,
+    V: TryFromValue,
+    D: DBusType + FromIterator<(K, V)>,
+{
+    match value {
+        Value::Array(array) if array.type_ == dict_entry_type::<K, V>() => array
+            .array
+            .into_iter()
+            .map(|entry| match entry {
+                Value::DictEntry(b) => {
+                    let (k, v) = *b;
+                    Ok((K::try_from_value(k)?, V::try_from_value(v)?))
+                }
+                entry => Err(type_mismatch(dict_entry_type::<K, V>(), &entry)),
+            })
+            .collect(),
+        value => Err(type_mismatch(D::dbus_type(), &value)),
+    }
+}
+
+impl<K: DBusBasicType, V: DBusType, S> DBusType for HashMap<K, V, S> {
+    fn dbus_type() -> Type {
+        Type::Array(Box::new(dict_entry_type::<K, V>()))
+    }
+}
+
+impl<K: DBusBasicType + IntoValue, V: IntoValue, S> IntoValue for HashMap<K, V, S> {
+    fn into_value(self) -> Value {
+        dict_into_value(self.into_iter())
+    }
+}
+
+impl<K, V, S> TryFromValue for HashMap<K, V, S>
+where
+    K: DBusBasicType + TryFromValue + Eq + Hash,
+    V: TryFromValue,
+    S: BuildHasher + Default,
+{
+    fn try_from_value(value: Value) -> Result<Self, ConvertError> {
+        dict_try_from_value(value)
+    }
+}
+
+impl<K: DBusBasicType, V: DBusType> DBusType for BTreeMap<K, V> {
+    fn dbus_type() -> Type {
+        Type::Array(Box::new(dict_entry_type::<K, V>()))
+    }
+}
+
+impl<K: DBusBasicType + IntoValue, V: IntoValue> IntoValue for BTreeMap<K, V> {
+    fn into_value(self) -> Value {
+        dict_into_value(self.into_iter())
+    }
+}
+
+impl<K, V> TryFromValue for BTreeMap<K, V>
+where
+    K: DBusBasicType + TryFromValue + Ord,
+    V: TryFromValue,
+{
+    fn try_from_value(value: Value) -> Result<Self, ConvertError> {
+        dict_try_from_value(value)
+    }
+}
+
+/// An [`Option`] is represented by a variant, because D-Bus has no null value. [`Some`] contains
+/// the value and [`None`] contains an empty array of the type of the value.
+impl<T: DBusType> DBusType for Option<T> {
+    fn dbus_type() -> Type {
+        Type::Variant
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        let value = match self {
+            Some(v) => v.into_value(),
+            None => Value::Array(Array {
+                type_: T::dbus_type(),
+                array: Vec::new(),
+            }),
+        };
+        Value::Variant(Box::new(value))
+    }
+}
+
+impl<T: TryFromValue> TryFromValue for Option<T> {
+    fn try_from_value(value: Value) -> Result<Self, ConvertError> {
+        match value {
+            Value::Variant(v) => match *v {
+                Value::Array(array) if array.array.is_empty() && array.type_ == T::dbus_type() => {
+                    Ok(None)
+                }
+                v => T::try_from_value(v).map(Some),
+            },
+            value => Err(type_mismatch(Type::Variant, &value)),
+        }
+    }
+}
+
+macro_rules! tuple {
+    ($len:expr, $($t:ident),+) => {
+        impl<$($t: DBusType),+> DBusType for ($($t,)+) {
+            fn dbus_type() -> Type {
+                Type::Struct(vec![$($t::dbus_type()),+])
+            }
+        }
+
+        impl<$($t: IntoValue),+> IntoValue for ($($t,)+) {
+            #[allow(non_snake_case)]
+            fn into_value(self) -> Value {
+                let ($($t,)+) = self;
+                Value::Struct(Struct(vec![$($t.into_value()),+]))
+            }
+        }
+
+        impl<$($t: TryFromValue),+> TryFromValue for ($($t,)+) {
+            fn try_from_value(value: Value) -> Result<Self, ConvertError> {
+                match value {
+                    Value::Struct(struct_) if struct_.0.len() == $len => {
+                        let mut values = struct_.0.into_iter();
+                        // The number of values is checked above.
+                        Ok(($($t::try_from_value(values.next().unwrap())?,)+))
+                    }
+                    value => Err(type_mismatch(Self::dbus_type(), &value)),
+                }
+            }
+        }
+    };
+}
+
+tuple!(1, A);
+tuple!(2, A, B);
+tuple!(3, A, B, C);
+tuple!(4, A, B, C, D);
+tuple!(5, A, B, C, D, E);
+tuple!(6, A, B, C, D, E, F);
+tuple!(7, A, B, C, D, E, F, G);
+tuple!(8, A, B, C, D, E, F, G, H);
+tuple!(9, A, B, C, D, E, F, G, H, I);
+tuple!(10, A, B, C, D, E, F, G, H, I, J);
+tuple!(11, A, B, C, D, E, F, G, H, I, J, K);
+tuple!(12, A, B, C, D, E, F, G, H, I, J, K, L);
@@ -0,0 +1,107 @@
+use crate::encode::algin;
+use crate::value::Type;
+
+/// Get the size of the framing offsets of a container in the GVariant format, which depends on
+/// the size of the whole container including the framing offsets.
+pub(crate) const fn gvariant_offset_size(container_size: usize) -> usize {
+    if container_size == 0 {
+        0
+    } else if container_size <= 0xff {
+        1
+    } else if container_size <= 0xffff {
+        2
+    } else if container_size <= 0xffff_ffff {
+        4
+    } else {
+        8
+    }
+}
+
+/// Get the alignment of a struct in the GVariant format, which is the biggest alignment of all
+/// members.
+pub(crate) fn gvariant_struct_alignment<'a, I>(types: I) -> usize
+where
+    I: Iterator<Item = &'a Type>,
+{
+    types.map(Type::get_gvariant_alignment).max().unwrap_or(1)
+}
+
+/// Get the size of a struct in the GVariant format, if all members have a fixed size.
+fn gvariant_struct_fixed_size<'a, I>(types: I) -> Option<usize>
+where
+    I: Iterator<Item = &'a Type> + Clone,
+{
+    let alignment = gvariant_struct_alignment(types.clone());
+    let mut size = 0;
+    let mut is_empty = true;
+    for type_ in types {
+        size = algin(size, type_.get_gvariant_alignment());
+        size += type_.get_gvariant_fixed_size()?;
+        is_empty = false;
+    }
+    if is_empty {
+        // The unit type has a size of one byte.
+        Some(1)
+    } else {
+        Some(algin(size, alignment))
+    }
+}
+
+impl Type {
+    /// Get the alignment of the type in the [GVariant format].
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub(crate) fn get_gvariant_alignment(&self) -> usize {
+        match self {
+            Type::Byte => 1,
+            Type::Boolean => 1,
+            Type::Int16 => 2,
+            Type::Uint16 => 2,
+            Type::Int32 => 4,
+            Type::Uint32 => 4,
+            Type::Int64 => 8,
+            Type::Uint64 => 8,
+            Type::UnixFD => 4,
+            Type::Double => 8,
+            Type::String => 1,
+            Type::ObjectPath => 1,
+            Type::Variant => 8,
+            Type::Signature => 1,
+            Type::Array(type_) => type_.get_gvariant_alignment(),
+            Type::Struct(types) => gvariant_struct_alignment(types.iter()),
+            Type::DictEntry(types) => types
+                .0
+                .get_gvariant_alignment()
+                .max(types.1.get_gvariant_alignment()),
+            Type::Maybe(type_) => type_.get_gvariant_alignment(),
+        }
+    }
+
+    /// Get the size of the type in the [GVariant format], if the type has a fixed size.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    pub(crate) fn get_gvariant_fixed_size(&self) -> Option<usize> {
+        match self {
+            Type::Byte => Some(1),
+            Type::Boolean => Some(1),
+            Type::Int16 => Some(2),
+            Type::Uint16 => Some(2),
+            Type::Int32 => Some(4),
+            Type::Uint32 => Some(4),
+            Type::Int64 => Some(8),
+            Type::Uint64 => Some(8),
+            Type::UnixFD => Some(4),
+            Type::Double => Some(8),
+            Type::String => None,
+            Type::ObjectPath => None,
+            Type::Variant => None,
+            Type::Signature => None,
+            Type::Array(_) => None,
+            Type::Struct(types) => gvariant_struct_fixed_size(types.iter()),
+            Type::DictEntry(types) => {
+                gvariant_struct_fixed_size([&types.0, &types.1].iter().copied())
+            }
+            Type::Maybe(_) => None,
+        }
+    }
+}
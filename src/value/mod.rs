@@ -1,23 +1,35 @@
 mod bus;
 mod container;
+mod convert;
 mod error;
+mod gvariant;
 mod interface;
 mod member;
 mod object_path;
 mod type_enum;
+#[cfg(target_family = "unix")]
+mod unix_fd;
 mod value_enum;
+mod value_ref;
 
 pub use bus::{
     Bus, BusError, UniqueConnectionName, UniqueConnectionNameError, WellKnownBusName,
     WellKnownBusNameError,
 };
-pub use container::{Array, ArrayError, Struct, StructError};
+pub use container::{Array, ArrayError, Maybe, Struct, StructError};
+pub use convert::{ConvertError, DBusBasicType, DBusType, IntoValue, TryFromValue};
 pub use error::{Error, ErrorError};
+pub(crate) use gvariant::{gvariant_offset_size, gvariant_struct_alignment};
 pub use interface::{Interface, InterfaceError};
 pub use member::{Member, MemberError};
 pub use object_path::{ObjectPath, ObjectPathError};
 pub use type_enum::{Type, TypeError, MAXIMUM_SIGNATURE_LENGTH};
+#[cfg(all(test, target_family = "unix"))]
+pub(crate) use unix_fd::open_null;
+#[cfg(target_family = "unix")]
+pub use unix_fd::UnixFd;
 pub use value_enum::Value;
+pub use value_ref::{ArrayRef, ValueRef};
 
 pub const MAXIMUM_NAME_LENGTH: usize = 255;
 pub const MAXIMUM_ARRAY_LENGTH: usize = 67108864;
@@ -83,7 +83,7 @@ fn check(error: &[u8]) -> Result<(), ObjectPathError> {
 ///
 /// [object path]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-marshaling-object-path
 #[derive(Debug, Clone, PartialOrd, PartialEq, Ord, Eq, Hash)]
-pub struct ObjectPath(String);
+pub struct ObjectPath(pub(crate) String);
 
 /// An enum representing all errors, which can occur during the handling of a [`ObjectPath`].
 #[derive(Debug, PartialEq, Eq, Error)]
@@ -161,6 +161,14 @@ impl PartialEq<str> for ObjectPath {
 }
 
 impl ObjectPath {
+    /// Check if the given bytes is a valid [object path].
+    ///
+    /// [object path]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-marshaling-object-path
+    #[inline]
+    pub(crate) fn check(object_path: &[u8]) -> Result<(), ObjectPathError> {
+        check(object_path)
+    }
+
     /// Append an element to the object path.
     ///
     /// Returns `true` if the new element could be appended, otherwise `false`.
@@ -238,6 +246,7 @@ impl ObjectPath {
     /// assert_eq!(path_3_base_vec, vec!["path", "element"]);
     /// assert!(base.strip_prefix_elements(&base).is_none());
     /// ```
+    #[allow(clippy::needless_lifetimes)]
     pub fn strip_prefix_elements<'a, 'b>(
         &'a self,
         base: &'b ObjectPath,
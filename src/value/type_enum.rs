@@ -11,11 +11,11 @@ pub const MAXIMUM_SIGNATURE_LENGTH: usize = 255;
 pub enum TypeError {
     #[error("Signature contians an invalid char: {0}")]
     InvalidChar(u8),
-    #[error("Array depth is too big: {MAXIMUM_ARRAY_DEPTH} < {0}")]
+    #[error("Array depth is too big: {0}")]
     ArrayDepth(u8),
-    #[error("Struct depth is too big: {MAXIMUM_STRUCT_DEPTH} < {0}")]
+    #[error("Struct depth is too big: {0}")]
     StructDepth(u8),
-    #[error("Dict depth is too big: {MAXIMUM_DICT_DEPTH} < {0}")]
+    #[error("Dict depth is too big: {0}")]
     DictDepth(u8),
     #[error("Signature is too big: {MAXIMUM_SIGNATURE_LENGTH} < {0}")]
     TooShort(usize, usize),
@@ -25,6 +25,10 @@ pub enum TypeError {
     MultiplyTypes,
     #[error("Signature must not exceed the maximum length: {MAXIMUM_SIGNATURE_LENGTH} < {0}")]
     ExceedMaximum(usize),
+    #[error("Dict entry must only be an element of an array")]
+    DictEntryPosition,
+    #[error("Key of a dict entry must be a basic type: {0}")]
+    DictEntryKey(Type),
 }
 
 /// Get the char at offset.
@@ -37,12 +41,15 @@ fn get_char_at(bytes: &[u8], offset: usize) -> Result<u8, TypeError> {
 }
 
 /// Get the next type from a `&str`.
+///
+/// A dict entry is only accepted, if `is_array_element` is `true`.
 fn next_type(
     type_string: &[u8],
     type_string_offset: &mut usize,
     array_depth: u8,
     struct_depth: u8,
     dict_depth: u8,
+    is_array_element: bool,
 ) -> Result<Type, TypeError> {
     Type::check_depth(array_depth, struct_depth, dict_depth)?;
 
@@ -61,9 +68,19 @@ fn next_type(
         b's' => Ok(Type::String),
         b'o' => Ok(Type::ObjectPath),
         b'g' => Ok(Type::Signature),
-        #[cfg(target_family = "unix")]
         b'h' => Ok(Type::UnixFD),
         b'v' => Ok(Type::Variant),
+        b'm' => {
+            let type_ = next_type(
+                type_string,
+                type_string_offset,
+                array_depth,
+                struct_depth,
+                dict_depth,
+                false,
+            )?;
+            Ok(Type::Maybe(Box::new(type_)))
+        }
         b'a' => {
             let type_ = next_type(
                 type_string,
@@ -71,6 +88,7 @@ fn next_type(
                 array_depth + 1,
                 struct_depth,
                 dict_depth,
+                true,
             )?;
             let type_ = Box::new(type_);
             Ok(Type::Array(type_))
@@ -82,6 +100,7 @@ fn next_type(
                 array_depth,
                 struct_depth + 1,
                 dict_depth,
+                false,
             )?;
             let mut types = vec![first_type];
             loop {
@@ -95,18 +114,27 @@ fn next_type(
                     array_depth,
                     struct_depth + 1,
                     dict_depth,
+                    false,
                 )?;
                 types.push(type_);
             }
         }
         b'{' => {
+            if !is_array_element {
+                return Err(TypeError::DictEntryPosition);
+            }
+
             let key = next_type(
                 type_string,
                 type_string_offset,
                 array_depth,
                 struct_depth,
                 dict_depth + 1,
+                false,
             )?;
+            if !key.is_basic() {
+                return Err(TypeError::DictEntryKey(key));
+            }
 
             let value = next_type(
                 type_string,
@@ -114,6 +142,7 @@ fn next_type(
                 array_depth,
                 struct_depth,
                 dict_depth + 1,
+                false,
             )?;
 
             match get_char_at(type_string, *type_string_offset)? {
@@ -142,7 +171,6 @@ pub enum Type {
     Uint32,
     Int64,
     Uint64,
-    #[cfg(target_family = "unix")]
     UnixFD,
     Double,
     String,
@@ -152,6 +180,11 @@ pub enum Type {
     Array(Box<Type>),
     Struct(Vec<Type>),
     DictEntry(Box<(Type, Type)>),
+    /// The maybe type of the [GVariant format]. It is not supported by the D-Bus format, so a
+    /// signature, which contains `m`, can only be decoded and encoded with the GVariant format.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    Maybe(Box<Type>),
 }
 
 impl Type {
@@ -199,7 +232,14 @@ impl Type {
         let mut signature = Vec::new();
         let mut signature_string_offset = 0;
         while signature_string_offset < signature_string_len {
-            let type_ = next_type(signature_string, &mut signature_string_offset, 0, 0, 0)?;
+            let type_ = next_type(
+                signature_string,
+                &mut signature_string_offset,
+                0,
+                0,
+                0,
+                false,
+            )?;
             signature.push(type_);
         }
 
@@ -209,6 +249,7 @@ impl Type {
     pub fn from_signature_to_string(signature: &[Type]) -> Result<String, TypeError> {
         let mut signature_string = String::new();
         for type_ in signature {
+            type_.check_dict_entries(false)?;
             type_.try_to_string(&mut signature_string, 0, 0, 0)?;
         }
         Ok(signature_string)
@@ -231,7 +272,6 @@ impl Type {
             Type::Uint32 => type_string.push('u'),
             Type::Int64 => type_string.push('x'),
             Type::Uint64 => type_string.push('t'),
-            #[cfg(target_family = "unix")]
             Type::UnixFD => type_string.push('h'),
             Type::Double => type_string.push('d'),
             Type::String => type_string.push('s'),
@@ -265,11 +305,73 @@ impl Type {
                 )?;
                 type_string.push('}');
             }
+            Type::Maybe(type_) => {
+                type_string.push('m');
+                type_.try_to_string(type_string, array_depth, struct_depth, dict_depth)?;
+            }
         }
         Type::check_len(type_string)?;
         Ok(())
     }
 
+    /// Returns `true` if the type is a basic type. Only basic types can be the key of a dict
+    /// entry.
+    pub const fn is_basic(&self) -> bool {
+        matches!(
+            self,
+            Type::Byte
+                | Type::Boolean
+                | Type::Int16
+                | Type::Uint16
+                | Type::Int32
+                | Type::Uint32
+                | Type::Int64
+                | Type::Uint64
+                | Type::UnixFD
+                | Type::Double
+                | Type::String
+                | Type::ObjectPath
+                | Type::Signature
+        )
+    }
+
+    /// Returns `true` if the type is or contains a [`Type::Maybe`]. Such a type can only be
+    /// used in the GVariant format.
+    pub(crate) fn contains_maybe(&self) -> bool {
+        match self {
+            Type::Array(type_) => type_.contains_maybe(),
+            Type::Struct(types) => types.iter().any(Type::contains_maybe),
+            Type::DictEntry(types) => types.0.contains_maybe() || types.1.contains_maybe(),
+            Type::Maybe(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check that every dict entry is an element of an array and that the key of every dict
+    /// entry is a basic type.
+    pub(crate) fn check_dict_entries(&self, is_array_element: bool) -> Result<(), TypeError> {
+        match self {
+            Type::Array(type_) => type_.check_dict_entries(true),
+            Type::Struct(types) => {
+                for type_ in types {
+                    type_.check_dict_entries(false)?;
+                }
+                Ok(())
+            }
+            Type::DictEntry(types) => {
+                if !is_array_element {
+                    return Err(TypeError::DictEntryPosition);
+                }
+                if !types.0.is_basic() {
+                    return Err(TypeError::DictEntryKey(types.0.clone()));
+                }
+                types.1.check_dict_entries(false)
+            }
+            Type::Maybe(type_) => type_.check_dict_entries(false),
+            _ => Ok(()),
+        }
+    }
+
     pub fn get_alignment(&self) -> usize {
         match self {
             Type::Byte => 1,
@@ -280,7 +382,6 @@ impl Type {
             Type::Uint32 => 4,
             Type::Int64 => 8,
             Type::Uint64 => 8,
-            #[cfg(target_family = "unix")]
             Type::UnixFD => 4,
             Type::Double => 8,
             Type::String => 4,
@@ -290,6 +391,7 @@ impl Type {
             Type::Array(_) => 4,
             Type::Struct(_) => 8,
             Type::DictEntry(_) => 8,
+            Type::Maybe(type_) => type_.get_alignment(),
         }
     }
 }
@@ -312,7 +414,7 @@ impl TryFrom<&[u8]> for Type {
         }
 
         let mut type_string_offset = 0;
-        let type_ = next_type(type_string, &mut type_string_offset, 0, 0, 0)?;
+        let type_ = next_type(type_string, &mut type_string_offset, 0, 0, 0, false)?;
         if type_string_len == type_string_offset {
             Ok(type_)
         } else {
@@ -332,7 +434,6 @@ impl Display for Type {
             Type::Uint32 => write!(f, "u"),
             Type::Int64 => write!(f, "x"),
             Type::Uint64 => write!(f, "t"),
-            #[cfg(target_family = "unix")]
             Type::UnixFD => write!(f, "h"),
             Type::Double => write!(f, "d"),
             Type::String => write!(f, "s"),
@@ -348,6 +449,7 @@ impl Display for Type {
                 write!(f, ")")
             }
             Type::DictEntry(dict_entry) => write!(f, "{{{}{}}}", dict_entry.0, dict_entry.1),
+            Type::Maybe(type_) => write!(f, "m{}", type_),
         }
     }
 }
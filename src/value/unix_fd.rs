@@ -0,0 +1,134 @@
+use std::cmp::Ordering;
+use std::convert::{From, TryFrom};
+use std::hash::{Hash, Hasher};
+use std::io::Result as IoResult;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+
+/// This represents an owned FD of a [`Value::UnixFD`].
+///
+/// The FD is closed, when the [`UnixFd`] is dropped, so the FD lives as long as the message,
+/// which carries it. Cloning a [`UnixFd`] duplicates the FD.
+///
+/// Two [`UnixFd`]s are compared by their FD numbers, so a clone is not equal to the original.
+///
+/// [`Value::UnixFD`]: crate::value::Value::UnixFD
+#[derive(Debug)]
+pub struct UnixFd(OwnedFd);
+
+impl UnixFd {
+    /// Duplicate the FD.
+    pub fn try_clone(&self) -> IoResult<UnixFd> {
+        let fd = self.0.try_clone()?;
+        Ok(UnixFd(fd))
+    }
+}
+
+impl Clone for UnixFd {
+    /// Duplicate the FD.
+    ///
+    /// # Panics
+    /// Panics if the FD could not be duplicated. Use [`try_clone`] to handle the error.
+    ///
+    /// [`try_clone`]: UnixFd::try_clone
+    fn clone(&self) -> Self {
+        self.try_clone().expect("Could not duplicate the FD")
+    }
+}
+
+impl From<OwnedFd> for UnixFd {
+    fn from(fd: OwnedFd) -> Self {
+        UnixFd(fd)
+    }
+}
+
+impl From<UnixFd> for OwnedFd {
+    fn from(fd: UnixFd) -> Self {
+        fd.0
+    }
+}
+
+impl<'a> TryFrom<BorrowedFd<'a>> for UnixFd {
+    type Error = std::io::Error;
+
+    /// Duplicate the borrowed FD to get an owned FD.
+    fn try_from(fd: BorrowedFd<'a>) -> Result<Self, Self::Error> {
+        let fd = fd.try_clone_to_owned()?;
+        Ok(UnixFd(fd))
+    }
+}
+
+impl AsFd for UnixFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for UnixFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for UnixFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+
+impl FromRawFd for UnixFd {
+    /// Take the ownership of the FD.
+    ///
+    /// # Safety
+    /// The FD has to be open and must not be owned by anything else.
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        UnixFd(OwnedFd::from_raw_fd(fd))
+    }
+}
+
+impl PartialEq for UnixFd {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_raw_fd() == other.as_raw_fd()
+    }
+}
+
+impl Eq for UnixFd {}
+
+impl PartialOrd for UnixFd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UnixFd {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_raw_fd().cmp(&other.as_raw_fd())
+    }
+}
+
+impl Hash for UnixFd {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_raw_fd().hash(state);
+    }
+}
+
+/// Open `/dev/null` to get a FD, which is closed, when it is dropped.
+#[cfg(test)]
+pub(crate) fn open_null() -> UnixFd {
+    let file = std::fs::File::open("/dev/null").unwrap();
+    UnixFd(OwnedFd::from(file))
+}
+
+#[test]
+fn clone_duplicates() {
+    let fd = open_null();
+    let fd_clone = fd.clone();
+    assert_ne!(fd, fd_clone);
+    assert_eq!(fd, fd);
+
+    let fd_borrowed = UnixFd::try_from(fd.as_fd()).unwrap();
+    assert_ne!(fd.as_raw_fd(), fd_borrowed.as_raw_fd());
+
+    let raw_fd = fd.as_raw_fd();
+    let owned_fd = OwnedFd::from(fd);
+    assert_eq!(owned_fd.as_raw_fd(), raw_fd);
+}
@@ -1,10 +1,20 @@
-use crate::value::{Array, ObjectPath, Struct, Type, TypeError};
 #[cfg(target_family = "unix")]
-use std::os::unix::io::RawFd;
+use crate::value::UnixFd;
+use crate::value::{Array, Maybe, ObjectPath, Struct, Type, TypeError};
 
 /// An enum representing a [DBus value].
 ///
+/// # Attached FDs
+/// A [`Value::UnixFD`] owns its FD, so cloning a `Value`, which contains attached FDs, duplicates
+/// every FD. [`Clone::clone`] panics if a FD could not be duplicated, for example because the
+/// process ran out of FDs. Use [`UnixFd::try_clone`] to handle this error.
+///
+/// Attached FDs are compared and ordered by their FD numbers and not by the files they refer to.
+/// A clone of a `Value`, which contains attached FDs, is therefore not equal to the original.
+///
 /// [DBus value]: https://dbus.freedesktop.org/doc/dbus-specification.html#type-system
+/// [`Value::UnixFD`]: crate::value::Value::UnixFD
+/// [`UnixFd::try_clone`]: crate::value::UnixFd::try_clone
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub enum Value {
     Byte(u8),
@@ -23,8 +33,17 @@ pub enum Value {
     Struct(Struct),
     DictEntry(Box<(Value, Value)>),
     Variant(Box<Value>),
+    /// A maybe value of the [GVariant format]. It is not supported by the D-Bus format.
+    ///
+    /// [GVariant format]: https://people.gnome.org/~desrt/gvariant-serialisation.pdf
+    Maybe(Maybe),
+    /// An attached FD, which is owned by the value. FDs can only be attached on Unix platforms.
     #[cfg(target_family = "unix")]
-    UnixFD(RawFd),
+    UnixFD(UnixFd),
+    /// The index of a FD in the FD array of the message, which is the encoded form of the `h`
+    /// type. It is available on all platforms, so a message can be decoded and encoded without
+    /// attaching the FDs.
+    UnixFdIndex(u32),
 }
 
 impl Value {
@@ -71,31 +90,28 @@ impl Value {
                 }
                 signature_string.push(')');
             }
-            Value::DictEntry(b) => {
-                signature_string.push('{');
-                let (key, value) = &**b;
-                key.to_signature_string(
-                    signature_string,
-                    array_depth,
-                    struct_depth,
-                    dict_depth + 1,
-                )?;
-                value.to_signature_string(
+            // A dict entry is only allowed as an element of an array, whose signature is
+            // written from the type of the array.
+            Value::DictEntry(_) => return Err(TypeError::DictEntryPosition),
+            Value::Variant(_) => signature_string.push('v'),
+            Value::Maybe(maybe) => {
+                signature_string.push('m');
+                maybe.get_type().try_to_string(
                     signature_string,
                     array_depth,
                     struct_depth,
-                    dict_depth + 1,
+                    dict_depth,
                 )?;
-                signature_string.push('}');
             }
-            Value::Variant(_) => signature_string.push('v'),
             #[cfg(target_family = "unix")]
             Value::UnixFD(_) => signature_string.push('h'),
+            Value::UnixFdIndex(_) => signature_string.push('h'),
         }
         Type::check_len(signature_string)?;
         Ok(())
     }
 
+    #[allow(clippy::wrong_self_convention)]
     fn from_value_to_type(
         &self,
         array_depth: u8,
@@ -114,6 +130,7 @@ impl Value {
             Value::Uint64(_) => Ok(Type::Uint64),
             #[cfg(target_family = "unix")]
             Value::UnixFD(_) => Ok(Type::UnixFD),
+            Value::UnixFdIndex(_) => Ok(Type::UnixFD),
             Value::Double(_) => Ok(Type::Double),
             Value::String(_) => Ok(Type::String),
             Value::ObjectPath(_) => Ok(Type::ObjectPath),
@@ -131,22 +148,142 @@ impl Value {
                 }
                 Ok(Type::Struct(signatures))
             }
+            // A dict entry is only allowed as an element of an array, see `get_element_type`.
+            Value::DictEntry(_) => Err(TypeError::DictEntryPosition),
+            Value::Variant(_) => Ok(Type::Variant),
+            Value::Maybe(maybe) => Ok(Type::Maybe(Box::new(maybe.get_type().clone()))),
+        }
+    }
+
+    /// Returns true if the `Value` contains an attached [`UnixFD`]. A [`UnixFdIndex`] is not
+    /// counted.
+    ///
+    /// [`UnixFD`]: crate::value::Value::UnixFD
+    /// [`UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    #[allow(clippy::unnecessary_map_or)]
+    pub(crate) fn has_unix_fds(&self) -> bool {
+        match self {
+            #[cfg(target_family = "unix")]
+            Value::UnixFD(_) => true,
+            Value::Array(array) => array.as_ref().iter().any(Value::has_unix_fds),
+            Value::Struct(struct_) => struct_.as_ref().iter().any(Value::has_unix_fds),
+            Value::DictEntry(b) => b.0.has_unix_fds() || b.1.has_unix_fds(),
+            Value::Variant(v) => v.has_unix_fds(),
+            Value::Maybe(maybe) => maybe.get_value().map_or(false, Value::has_unix_fds),
+            _ => false,
+        }
+    }
+
+    /// Call `f` for every [`UnixFD`] and every [`UnixFdIndex`] in the `Value` in the order, in
+    /// which they are encoded.
+    ///
+    /// [`UnixFD`]: crate::value::Value::UnixFD
+    /// [`UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    pub(crate) fn for_each_unix_fd<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&'a Value),
+    {
+        match self {
+            #[cfg(target_family = "unix")]
+            Value::UnixFD(_) => f(self),
+            Value::UnixFdIndex(_) => f(self),
+            Value::Array(array) => array.array.iter().for_each(|v| v.for_each_unix_fd(f)),
+            Value::Struct(struct_) => struct_.0.iter().for_each(|v| v.for_each_unix_fd(f)),
             Value::DictEntry(b) => {
-                let key_signature =
-                    b.0.from_value_to_type(array_depth, struct_depth, dict_depth + 1)?;
-                let value_signature =
-                    b.1.from_value_to_type(array_depth, struct_depth, dict_depth + 1)?;
-                let signature = Box::new((key_signature, value_signature));
-                Ok(Type::DictEntry(signature))
+                b.0.for_each_unix_fd(f);
+                b.1.for_each_unix_fd(f);
             }
-            Value::Variant(_) => Ok(Type::Variant),
+            Value::Variant(v) => v.for_each_unix_fd(f),
+            Value::Maybe(maybe) => {
+                if let Some(v) = &maybe.value {
+                    v.for_each_unix_fd(f);
+                }
+            }
+            _ => {}
         }
     }
 
+    /// The mutable version of [`for_each_unix_fd`]. `f` can replace the `Value`.
+    ///
+    /// [`for_each_unix_fd`]: crate::value::Value::for_each_unix_fd
+    pub(crate) fn for_each_unix_fd_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut Value),
+    {
+        match self {
+            #[cfg(target_family = "unix")]
+            Value::UnixFD(_) => f(self),
+            Value::UnixFdIndex(_) => f(self),
+            Value::Array(array) => array
+                .array
+                .iter_mut()
+                .for_each(|v| v.for_each_unix_fd_mut(f)),
+            Value::Struct(struct_) => struct_.0.iter_mut().for_each(|v| v.for_each_unix_fd_mut(f)),
+            Value::DictEntry(b) => {
+                b.0.for_each_unix_fd_mut(f);
+                b.1.for_each_unix_fd_mut(f);
+            }
+            Value::Variant(v) => v.for_each_unix_fd_mut(f),
+            Value::Maybe(maybe) => {
+                if let Some(v) = &mut maybe.value {
+                    v.for_each_unix_fd_mut(f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace the index of every [`UnixFdIndex`] in the `Value` by the result of `f`.
+    ///
+    /// This is needed, if the `Value` is copied from a message into another message, which has a
+    /// different FD array.
+    ///
+    /// # Example
+    /// ```
+    /// # use dbus_message_parser::value::{Struct, Value};
+    /// # use std::convert::TryFrom;
+    /// let struct_ = Struct::try_from(vec![Value::UnixFdIndex(0), Value::UnixFdIndex(1)]).unwrap();
+    /// let mut value = Value::Struct(struct_);
+    /// value.remap_unix_fd_indices(|i| i + 2);
+    ///
+    /// let struct_ = Struct::try_from(vec![Value::UnixFdIndex(2), Value::UnixFdIndex(3)]).unwrap();
+    /// assert_eq!(value, Value::Struct(struct_));
+    /// ```
+    ///
+    /// [`UnixFdIndex`]: crate::value::Value::UnixFdIndex
+    pub fn remap_unix_fd_indices<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u32) -> u32,
+    {
+        self.for_each_unix_fd_mut(&mut |v| {
+            if let Value::UnixFdIndex(i) = v {
+                *i = f(*i);
+            }
+        });
+    }
+
     /// Returns the [`Type`] of the `Value`.
     ///
     /// [`Type`]: crate::value::Type
     pub fn get_type(&self) -> Result<Type, TypeError> {
         self.from_value_to_type(0, 0, 0)
     }
+
+    /// Returns the [`Type`] of the `Value` as an element of an array. In contrast to
+    /// [`get_type`], the `Value` can be a dict entry.
+    ///
+    /// [`Type`]: crate::value::Type
+    /// [`get_type`]: crate::value::Value::get_type
+    pub(crate) fn get_element_type(&self) -> Result<Type, TypeError> {
+        if let Value::DictEntry(b) = self {
+            let key_type = b.0.from_value_to_type(0, 0, 1)?;
+            if !key_type.is_basic() {
+                return Err(TypeError::DictEntryKey(key_type));
+            }
+            let value_type = b.1.from_value_to_type(0, 0, 1)?;
+            Ok(Type::DictEntry(Box::new((key_type, value_type))))
+        } else {
+            self.from_value_to_type(0, 0, 0)
+        }
+    }
 }
@@ -0,0 +1,110 @@
+#[cfg(target_family = "unix")]
+use crate::value::UnixFd;
+use crate::value::{Array, ObjectPath, Struct, Type, Value};
+use std::convert::AsRef;
+
+/// An enum representing a borrowed [DBus value].
+///
+/// In contrast to [`Value`], strings, object paths and byte arrays reference the buffer from which
+/// they were decoded, so they are not copied. Use [`to_value`] to convert it into a [`Value`].
+///
+/// [DBus value]: https://dbus.freedesktop.org/doc/dbus-specification.html#type-system
+/// [`to_value`]: ValueRef::to_value
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub enum ValueRef<'a> {
+    Byte(u8),
+    Boolean(bool),
+    Int16(i16),
+    Uint16(u16),
+    Int32(i32),
+    Uint32(u32),
+    Int64(i64),
+    Uint64(u64),
+    Double(f64),
+    String(&'a str),
+    /// An [object path], which is already validated.
+    ///
+    /// [object path]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-marshaling-object-path
+    ObjectPath(&'a str),
+    Signature(Vec<Type>),
+    Array(ArrayRef<'a>),
+    /// An array of bytes (`ay`), which is not split into single values.
+    ByteArray(&'a [u8]),
+    Struct(Vec<ValueRef<'a>>),
+    DictEntry(Box<(ValueRef<'a>, ValueRef<'a>)>),
+    Variant(Box<ValueRef<'a>>),
+    #[cfg(target_family = "unix")]
+    UnixFD(&'a UnixFd),
+    UnixFdIndex(u32),
+}
+
+/// A borrowed array, which is the counterpart of [`Array`].
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct ArrayRef<'a> {
+    pub(crate) type_: Type,
+    pub(crate) array: Vec<ValueRef<'a>>,
+}
+
+impl<'a> ArrayRef<'a> {
+    #[inline]
+    pub const fn get_type(&self) -> &Type {
+        &self.type_
+    }
+
+    /// Convert the [`ArrayRef`] into an owned [`Array`].
+    pub fn to_array(&self) -> Array {
+        let array = self.array.iter().map(ValueRef::to_value).collect();
+        Array {
+            type_: self.type_.clone(),
+            array,
+        }
+    }
+}
+
+impl<'a> AsRef<[ValueRef<'a>]> for ArrayRef<'a> {
+    fn as_ref(&self) -> &[ValueRef<'a>] {
+        self.array.as_ref()
+    }
+}
+
+impl<'a> ValueRef<'a> {
+    /// Convert the [`ValueRef`] into an owned [`Value`] by copying all the referenced strings and
+    /// duplicating all the referenced FDs.
+    pub fn to_value(&self) -> Value {
+        match self {
+            ValueRef::Byte(b) => Value::Byte(*b),
+            ValueRef::Boolean(b) => Value::Boolean(*b),
+            ValueRef::Int16(i) => Value::Int16(*i),
+            ValueRef::Uint16(u) => Value::Uint16(*u),
+            ValueRef::Int32(i) => Value::Int32(*i),
+            ValueRef::Uint32(u) => Value::Uint32(*u),
+            ValueRef::Int64(i) => Value::Int64(*i),
+            ValueRef::Uint64(u) => Value::Uint64(*u),
+            ValueRef::Double(f) => Value::Double(*f),
+            ValueRef::String(s) => Value::String((*s).to_owned()),
+            // The object path was already checked during the decoding.
+            ValueRef::ObjectPath(o) => Value::ObjectPath(ObjectPath((*o).to_owned())),
+            ValueRef::Signature(signature) => Value::Signature(signature.clone()),
+            ValueRef::Array(array) => Value::Array(array.to_array()),
+            ValueRef::ByteArray(bytes) => {
+                let array = bytes.iter().map(|b| Value::Byte(*b)).collect();
+                Value::Array(Array {
+                    type_: Type::Byte,
+                    array,
+                })
+            }
+            ValueRef::Struct(values) => {
+                let values = values.iter().map(ValueRef::to_value).collect();
+                Value::Struct(Struct(values))
+            }
+            ValueRef::DictEntry(b) => {
+                let (key, value) = &**b;
+                Value::DictEntry(Box::new((key.to_value(), value.to_value())))
+            }
+            ValueRef::Variant(v) => Value::Variant(Box::new(v.to_value())),
+            #[cfg(target_family = "unix")]
+            ValueRef::UnixFD(fd) => Value::UnixFD((*fd).clone()),
+            ValueRef::UnixFdIndex(i) => Value::UnixFdIndex(*i),
+        }
+    }
+}
@@ -78,7 +78,6 @@ fn type_uint64() {
 }
 
 #[test]
-#[cfg(target_family = "unix")]
 fn type_unix_fd() {
     assert_eq!(&Type::UnixFD.to_string(), "h");
 }
@@ -48,7 +48,6 @@ fn error_4() {
     );
 }
 
-#[cfg(target_family = "unix")]
 #[test]
 fn error_5() {
     let variant = Value::Variant(Box::new(Value::String("".to_string())));
@@ -65,14 +64,26 @@ fn error_5() {
 #[test]
 fn error_6() {
     let variant = Value::Variant(Box::new(Value::Uint32(10)));
-    let struct_ = Struct::try_from(vec![Value::Byte(10), variant]).unwrap();
+    let struct_ = Struct::try_from(vec![Value::Byte(0), variant]).unwrap();
     let values = vec![Value::Struct(struct_)];
     assert_eq!(
         MessageHeaderFields::try_from(values),
-        Err(MessageHeaderFieldsError::InvalidNumber(10))
+        Err(MessageHeaderFieldsError::InvalidNumber(0))
     );
 }
 
+#[test]
+fn unknown() {
+    let variant = Value::Variant(Box::new(Value::Uint32(10)));
+    let struct_ = Struct::try_from(vec![Value::Byte(10), variant]).unwrap();
+    let values = vec![Value::Struct(struct_)];
+    let fields = MessageHeaderFields::try_from(values.clone()).unwrap();
+    assert_eq!(fields.unknown, vec![(10, Value::Uint32(10))]);
+
+    let values_2: Vec<Value> = fields.into();
+    assert_eq!(values, values_2);
+}
+
 #[test]
 fn path() {
     let variant = Value::Variant(Box::new(Value::ObjectPath(
@@ -438,6 +449,7 @@ fn sender_error_1() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn sender_error_2() {
     let variant = Value::Variant(Box::new(Value::String(
         "/org.example.sender".try_into().unwrap(),
@@ -513,7 +525,6 @@ fn signature_error_2() {
     );
 }
 
-#[cfg(target_family = "unix")]
 #[test]
 fn unix_fds_error() {
     let unix_fds = Value::Uint32(10);
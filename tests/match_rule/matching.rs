@@ -278,6 +278,7 @@ fn matching_rule_arg_3() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn matching_rule_arg_path_1() {
     let match_rule =
         MatchRule::ArgPath(ArgPath::try_from((0, ObjectPath::try_from("/a").unwrap())).unwrap());
@@ -296,6 +297,7 @@ fn matching_rule_arg_path_1() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn matching_rule_arg_path_2() {
     let match_rule =
         MatchRule::ArgPath(ArgPath::try_from((0, ObjectPath::try_from("/a").unwrap())).unwrap());
@@ -314,6 +316,7 @@ fn matching_rule_arg_path_2() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn matching_rule_arg_path_3() {
     let match_rule =
         MatchRule::ArgPath(ArgPath::try_from((0, ObjectPath::try_from("/a").unwrap())).unwrap());
@@ -328,6 +331,7 @@ fn matching_rule_arg_path_3() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn matching_rule_arg_path_4() {
     let match_rule =
         MatchRule::ArgPath(ArgPath::try_from((0, ObjectPath::try_from("/a").unwrap())).unwrap());
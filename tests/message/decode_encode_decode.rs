@@ -1,6 +1,14 @@
+#[cfg(target_family = "unix")]
+use super::{open_fds, raw_fds};
 use bytes::Bytes;
 use dbus_message_parser::message::Message;
+#[cfg(target_family = "unix")]
+use dbus_message_parser::value::{UnixFd, Value};
 use std::cmp::Ordering;
+#[cfg(target_family = "unix")]
+use std::convert::TryFrom;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::{AsRawFd, RawFd};
 
 fn decode_encode_decode(msg: &[u8]) {
     // Decode Bytes to message
@@ -9,6 +17,7 @@ fn decode_encode_decode(msg: &[u8]) {
 
     // Encode message to BytesMut
     let bytes = msg_1.encode().unwrap();
+    assert_eq!(msg_1.encoded_len(), bytes.len());
 
     // Decode message again
     let (msg_2, _) = Message::decode(bytes.freeze()).unwrap();
@@ -90,7 +99,7 @@ fn msg_4() {
 #[test]
 #[cfg(target_family = "unix")]
 fn msg_5() {
-    let fds = [1, 2];
+    let fds = open_fds(2);
     let msg = b"\x6c\x01\x00\x01\x08\x00\x00\x00\x00\x00\x00\x00\x68\x00\x00\x00\x01\x01\x6f\x00\
     \x0c\x00\x00\x00\x2f\x6f\x62\x6a\x65\x63\x74\x2f\x70\x61\x74\x68\x00\x00\x00\x00\x02\x01\x73\
     \x00\x11\x00\x00\x00\x69\x6e\x74\x65\x72\x66\x61\x63\x65\x2e\x65\x78\x61\x6d\x70\x6c\x65\x00\
@@ -104,23 +113,45 @@ fn msg_5() {
     assert_eq!(msg.len(), offset);
     assert!(msg_1.has_unix_fds());
 
+    // The decoded message owns duplicates of the FDs.
+    let body_fds: Vec<RawFd> = msg_1
+        .get_body()
+        .iter()
+        .map(|v| match v {
+            Value::UnixFD(fd) => fd.as_raw_fd(),
+            v => panic!("Value is not a FD: {:?}", v),
+        })
+        .collect();
+    assert_ne!(raw_fds(&fds), body_fds);
+
     let (bytes, fds_2) = msg_1.encode_with_fds().unwrap();
     let bytes = bytes.freeze();
-    assert_eq!(&fds[..], &fds_2[..]);
+    assert_eq!(body_fds, raw_fds(&fds_2));
+    let fds_2: Vec<UnixFd> = fds_2
+        .into_iter()
+        .map(|fd| UnixFd::try_from(fd).unwrap())
+        .collect();
 
     let (msg_2, offset_2, offset_fds_2) =
         Message::decode_with_fds(bytes.clone(), &fds_2[..]).unwrap();
     assert_eq!(offset_fds_2, fds_2.len());
     assert_eq!(bytes.len(), offset_2);
     assert_eq!(msg_2.get_unix_fds(), Some(2));
+    assert_eq!(msg_1.get_signature(), msg_2.get_signature());
+}
 
-    // Check if it is equal
-    if let Some(ordering) = msg_1.partial_cmp(&msg_2) {
-        match ordering {
-            Ordering::Equal => {}
-            ordering => {
-                panic!("\n{:?}\nleft:  {:?}\nright: {:?}", ordering, msg_1, msg_2);
-            }
-        }
-    }
+#[test]
+fn unknown_flags() {
+    let msg = b"\x6c\x02\x81\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\x73\x00\
+    \x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\x67\
+    \x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+    \x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\
+    \x00";
+
+    decode_encode_decode(&msg[..]);
+
+    let bytes = Bytes::copy_from_slice(&msg[..]);
+    let (msg, _) = Message::decode(bytes).unwrap();
+    let bytes = msg.encode().unwrap();
+    assert_eq!(bytes[2], 0x81);
 }
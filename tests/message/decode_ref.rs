@@ -0,0 +1,110 @@
+#[cfg(target_family = "unix")]
+use super::open_fds;
+use bytes::Bytes;
+use dbus_message_parser::message::Message;
+use dbus_message_parser::value::{Array, Type, Value, ValueRef};
+use std::convert::TryInto;
+
+fn decode_ref(msg: &[u8]) {
+    let (header, body_ref, offset_ref) = Message::decode_ref(msg).unwrap();
+
+    let bytes = Bytes::copy_from_slice(msg);
+    let (msg, offset) = Message::decode(bytes).unwrap();
+    assert_eq!(offset_ref, offset);
+    assert_eq!(header.get_serial(), msg.get_serial());
+
+    let body: Vec<Value> = body_ref.iter().map(ValueRef::to_value).collect();
+    assert_eq!(&body[..], msg.get_body());
+}
+
+#[test]
+fn msg_1() {
+    let msg = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\x73\x00\
+    \x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\x67\
+    \x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+    \x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\
+    \x00";
+
+    decode_ref(&msg[..]);
+
+    let (_, body, _) = Message::decode_ref(&msg[..]).unwrap();
+    assert_eq!(body, vec![ValueRef::String(":1.98")]);
+}
+
+#[test]
+fn byte_array() {
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    let array = vec![Value::Byte(1), Value::Byte(2), Value::Byte(3)];
+    msg.add_value(Value::Array(Array::new(array, Type::Byte).unwrap()));
+    let bytes = msg.encode().unwrap();
+
+    decode_ref(&bytes);
+
+    let (_, body, _) = Message::decode_ref(&bytes).unwrap();
+    assert_eq!(body, vec![ValueRef::ByteArray(&[1, 2, 3])]);
+}
+
+#[test]
+fn msg_2() {
+    let msg = b"\x6c\x02\x01\x01\xec\x00\x00\x00\x60\x00\x00\x00\x36\x00\x00\x00\x06\x01\x73\x00\
+    \x06\x00\x00\x00\x3a\x31\x2e\x31\x30\x35\x00\x00\x08\x01\x67\x00\x0a\x61\x7b\x73\x28\x62\x67\
+    \x61\x76\x29\x7d\x00\x05\x01\x75\x00\x39\x01\x00\x00\x07\x01\x73\x00\x05\x00\x00\x00\x3a\x31\
+    \x2e\x39\x39\x00\x00\x00\xe4\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x71\x75\x69\x74\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x0c\x00\x00\x00\x6e\x65\x77\x2d\x64\x6f\x63\x75\x6d\x65\x6e\x74\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x0b\x00\x00\x00\x70\x72\
+    \x65\x66\x65\x72\x65\x6e\x63\x65\x73\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x00\x00\x09\x00\x00\x00\x73\x68\x6f\x72\x74\x63\x75\x74\x73\x00\x00\x00\x01\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x68\x65\x6c\x70\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\
+    \x00\x00\x61\x62\x6f\x75\x74\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x0a\x00\x00\x00\x6e\x65\x77\x2d\x77\x69\x6e\x64\x6f\x77\x00\x00\
+    \x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+
+    decode_ref(&msg[..]);
+}
+
+#[test]
+fn msg_3() {
+    let msg = b"\x6c\x04\x01\x01\x74\x00\x00\x00\xb8\x00\x00\x00\x76\x00\x00\x00\x01\x01\x6f\x00\
+    \x10\x00\x00\x00\x2f\x6f\x72\x67\x2f\x67\x6e\x6f\x6d\x65\x2f\x64\x66\x65\x65\x74\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x02\x01\x73\x00\x0f\x00\x00\x00\x6f\x72\x67\x2e\x67\x74\x6b\x2e\x41\x63\
+    \x74\x69\x6f\x6e\x73\x00\x08\x01\x67\x00\x16\x61\x73\x61\x7b\x73\x62\x7d\x61\x7b\x73\x76\x7d\
+    \x61\x7b\x73\x28\x62\x67\x61\x76\x29\x7d\x00\x00\x00\x00\x00\x03\x01\x73\x00\x07\x00\x00\x00\
+    \x43\x68\x61\x6e\x67\x65\x64\x00\x07\x01\x73\x00\x05\x00\x00\x00\x3a\x31\x2e\x38\x39\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x5c\x00\x00\x00\x00\x00\
+    \x00\x00\x04\x00\x00\x00\x68\x65\x6c\x70\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\x00\x00\x61\x62\x6f\x75\x74\x00\x00\x00\
+    \x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\
+    \x00\x71\x75\x69\x74\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x00";
+
+    decode_ref(&msg[..]);
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn msg_4() {
+    let fds = open_fds(2);
+    let msg = b"\x6c\x01\x00\x01\x08\x00\x00\x00\x00\x00\x00\x00\x68\x00\x00\x00\x01\x01\x6f\x00\
+    \x0c\x00\x00\x00\x2f\x6f\x62\x6a\x65\x63\x74\x2f\x70\x61\x74\x68\x00\x00\x00\x00\x02\x01\x73\
+    \x00\x11\x00\x00\x00\x69\x6e\x74\x65\x72\x66\x61\x63\x65\x2e\x65\x78\x61\x6d\x70\x6c\x65\x00\
+    \x00\x00\x00\x00\x00\x00\x03\x01\x73\x00\x06\x00\x00\x00\x4d\x65\x6d\x62\x65\x72\x00\x00\x06\
+    \x01\x73\x00\x04\x00\x00\x00\x3a\x31\x2e\x31\x00\x00\x00\x00\x08\x01\x67\x00\x02\x68\x68\x00\
+    \x09\x01\x75\x00\x02\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00";
+
+    let (header, body, offset, offset_fds) =
+        Message::decode_ref_with_fds(&msg[..], &fds[..]).unwrap();
+    assert_eq!(header.get_unix_fds(), Some(2));
+    // The body borrows the FDs.
+    assert_eq!(
+        body,
+        vec![ValueRef::UnixFD(&fds[0]), ValueRef::UnixFD(&fds[1])]
+    );
+    assert_eq!(offset, msg.len());
+    assert_eq!(offset_fds, fds.len());
+}
@@ -1,6 +1,12 @@
+#[cfg(target_family = "unix")]
+use super::{open_fds, raw_fds};
+use bytes::BytesMut;
+use dbus_message_parser::encode::EncodeError;
 use dbus_message_parser::message::Message;
-use dbus_message_parser::value::Value;
+use dbus_message_parser::value::{Maybe, Type, TypeError, Value};
 use std::convert::TryInto;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::{AsFd, AsRawFd, RawFd};
 
 #[test]
 fn method_call() {
@@ -49,3 +55,108 @@ fn signal() {
         \xf0\x3f"[..],
     );
 }
+
+fn create_signal() -> Message {
+    let mut signal = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    signal.add_value(Value::Uint32(0));
+    signal.add_value(Value::Double(1.0));
+    signal
+}
+
+#[test]
+fn encode_into() {
+    let signal = create_signal();
+    let bytes = signal.encode().unwrap();
+
+    // The alignment is relative to the start of the message.
+    let mut buf = BytesMut::from(&b"abc"[..]);
+    signal.encode_into(&mut buf).unwrap();
+    signal.encode_into(&mut buf).unwrap();
+    assert_eq!(&buf[..3], b"abc");
+    assert_eq!(&buf[3..3 + bytes.len()], &bytes[..]);
+    assert_eq!(&buf[3 + bytes.len()..], &bytes[..]);
+}
+
+#[test]
+fn encode_into_error() {
+    let mut value = Value::Byte(0);
+    for _ in 0..64 {
+        value = Value::Struct(vec![value].try_into().unwrap());
+    }
+    let mut signal = create_signal();
+    signal.add_value(Value::Variant(Box::new(value)));
+
+    let mut buf = BytesMut::from(&b"abc"[..]);
+    assert!(signal.encode_into(&mut buf).is_err());
+    assert_eq!(&buf[..], b"abc");
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn encode_into_with_fds() {
+    let fds = open_fds(3);
+    let mut signal = create_signal();
+    signal.add_value(Value::UnixFD(fds[1].clone()));
+    signal.add_value(Value::UnixFD(fds[2].clone()));
+    let body_fds: Vec<RawFd> = signal
+        .get_body()
+        .iter()
+        .filter_map(|v| match v {
+            Value::UnixFD(fd) => Some(fd.as_raw_fd()),
+            _ => None,
+        })
+        .collect();
+    let (bytes, fds_encoded) = signal.encode_with_fds().unwrap();
+    assert_eq!(raw_fds(&fds_encoded), body_fds);
+    assert_eq!(signal.encoded_len(), bytes.len());
+
+    let mut buf = BytesMut::new();
+    let mut fds_encoded = vec![fds[0].as_fd()];
+    signal
+        .encode_into_with_fds(&mut buf, &mut fds_encoded)
+        .unwrap();
+    assert_eq!(buf, bytes);
+    assert_eq!(
+        raw_fds(&fds_encoded),
+        vec![fds[0].as_raw_fd(), body_fds[0], body_fds[1]]
+    );
+
+    let (msg, _, offset_fds) = Message::decode_with_fds(buf.freeze(), &fds[1..]).unwrap();
+    assert_eq!(msg.get_unix_fds(), Some(2));
+    assert_eq!(offset_fds, 2);
+    assert_eq!(msg.get_signature(), signal.get_signature());
+}
+
+#[test]
+fn encode_maybe_error() {
+    let mut signal = create_signal();
+    signal.add_value(Value::Maybe(Maybe::nothing(Type::Int32)));
+    assert_eq!(signal.encode(), Err(EncodeError::MaybeType));
+}
+
+#[test]
+fn encode_dict_entry_error() {
+    let mut signal = create_signal();
+    signal.add_value(Value::DictEntry(Box::new((
+        Value::String("key".to_string()),
+        Value::Int32(1),
+    ))));
+    assert_eq!(
+        signal.encode(),
+        Err(EncodeError::SignatureError(TypeError::DictEntryPosition))
+    );
+}
+
+#[test]
+fn encode_string_nul_error() {
+    let mut signal = create_signal();
+    signal.add_value(Value::String("a\0b".to_string()));
+    assert_eq!(
+        signal.encode(),
+        Err(EncodeError::StringNul("a\0b".to_string()))
+    );
+}
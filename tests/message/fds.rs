@@ -0,0 +1,198 @@
+#[cfg(target_family = "unix")]
+use super::open_fds;
+use bytes::Bytes;
+#[cfg(target_family = "unix")]
+use dbus_message_parser::decode::MessageStreamDecoder;
+use dbus_message_parser::decode::{DecodeError, DecodePathElement};
+use dbus_message_parser::encode::EncodeError;
+use dbus_message_parser::message::{LazyMessage, Message, ValidationError};
+use dbus_message_parser::value::{Value, ValueRef};
+use std::convert::TryInto;
+
+/// A signal with the body signature `hh`, which references the FDs 0 and 1, and the `UNIX_FDS`
+/// header field 2.
+static MSG: &[u8] = b"\x6c\x01\x00\x01\x08\x00\x00\x00\x00\x00\x00\x00\x68\x00\x00\x00\x01\x01\x6f\
+\x00\x0c\x00\x00\x00\x2f\x6f\x62\x6a\x65\x63\x74\x2f\x70\x61\x74\x68\x00\x00\x00\x00\x02\x01\x73\
+\x00\x11\x00\x00\x00\x69\x6e\x74\x65\x72\x66\x61\x63\x65\x2e\x65\x78\x61\x6d\x70\x6c\x65\x00\x00\
+\x00\x00\x00\x00\x00\x03\x01\x73\x00\x06\x00\x00\x00\x4d\x65\x6d\x62\x65\x72\x00\x00\x06\x01\x73\
+\x00\x04\x00\x00\x00\x3a\x31\x2e\x31\x00\x00\x00\x00\x08\x01\x67\x00\x02\x68\x68\x00\x09\x01\x75\
+\x00\x02\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00";
+
+/// Create the message with the given `UNIX_FDS` header field and the given FD indices.
+fn create_msg(unix_fds: u8, index_1: u8, index_2: u8) -> Bytes {
+    let mut msg = MSG.to_vec();
+    let len = msg.len();
+    msg[len - 12] = unix_fds;
+    msg[len - 8] = index_1;
+    msg[len - 4] = index_2;
+    Bytes::from(msg)
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unused_fds() {
+    let fds = open_fds(4);
+    let msg = create_msg(3, 0, 0);
+    let (msg, _, offset_fds) = Message::decode_with_fds(msg, &fds).unwrap();
+    assert_eq!(msg.get_unix_fds(), Some(3));
+    assert_eq!(offset_fds, 3);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unused_fds_ref() {
+    let fds = open_fds(4);
+    let msg = create_msg(3, 1, 1);
+    let (header, _, _, offset_fds) = Message::decode_ref_with_fds(&msg, &fds).unwrap();
+    assert_eq!(header.get_unix_fds(), Some(3));
+    assert_eq!(offset_fds, 3);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn index_error() {
+    let fds = open_fds(3);
+    let msg = create_msg(2, 0, 2);
+    assert_eq!(
+        Message::decode_with_fds(msg.clone(), &fds).map_err(DecodeError::from),
+        Err(DecodeError::UnixFdIndex(2, 2))
+    );
+    assert_eq!(
+        Message::decode_ref_with_fds(&msg, &fds)
+            .map(|_| ())
+            .map_err(DecodeError::from),
+        Err(DecodeError::UnixFdIndex(2, 2))
+    );
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn not_enough_fds_error() {
+    let fds = open_fds(2);
+    let msg = create_msg(3, 0, 1);
+    assert_eq!(
+        Message::decode_with_fds(msg, &fds).map_err(DecodeError::from),
+        Err(DecodeError::NotEnoughFds(2, 3))
+    );
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn stream_unused_fds() {
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(&create_msg(3, 0, 0));
+    decoder.extend_fds(open_fds(4));
+    let msg = decoder.decode().unwrap().unwrap();
+    assert_eq!(msg.get_unix_fds(), Some(3));
+    assert_eq!(decoder.get_fds().len(), 1);
+}
+
+#[test]
+fn decode_index() {
+    let bytes = create_msg(2, 0, 1);
+    let (msg, offset) = Message::decode(bytes.clone()).unwrap();
+    assert_eq!(offset, bytes.len());
+    assert_eq!(msg.get_unix_fds(), Some(2));
+    assert_eq!(
+        msg.get_body(),
+        &[Value::UnixFdIndex(0), Value::UnixFdIndex(1)]
+    );
+    assert_eq!(msg.validate(), Ok(()));
+    assert_eq!(&msg.encode().unwrap()[..], &bytes[..]);
+}
+
+#[test]
+fn decode_ref_index() {
+    let bytes = create_msg(2, 1, 0);
+    let (_, body, _) = Message::decode_ref(&bytes).unwrap();
+    assert_eq!(
+        body,
+        vec![ValueRef::UnixFdIndex(1), ValueRef::UnixFdIndex(0)]
+    );
+}
+
+#[test]
+fn decode_lazy_index() {
+    let bytes = create_msg(2, 1, 1);
+    let (msg, _) = LazyMessage::decode(bytes).unwrap();
+    assert_eq!(
+        msg.decode_body(),
+        Ok(vec![Value::UnixFdIndex(1), Value::UnixFdIndex(1)])
+    );
+}
+
+#[test]
+fn decode_index_error() {
+    let bytes = create_msg(2, 0, 2);
+    let error = Message::decode(bytes).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::UnixFdIndex(2, 2));
+    assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(1)]);
+}
+
+#[test]
+fn validate_index_error() {
+    let (mut msg, _) = Message::decode(create_msg(2, 0, 1)).unwrap();
+    msg.add_value(Value::UnixFdIndex(2));
+    assert_eq!(
+        msg.validate(),
+        Err(vec![ValidationError::UnixFdIndex(2, 2)])
+    );
+}
+
+#[test]
+fn encode_index_error() {
+    let (mut msg, _) = Message::decode(create_msg(2, 0, 1)).unwrap();
+    msg.add_value(Value::UnixFdIndex(2));
+    assert_eq!(msg.encode(), Err(EncodeError::UnixFdIndex(2, 2)));
+
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    );
+    msg.add_value(Value::UnixFdIndex(0));
+    assert_eq!(msg.encode(), Err(EncodeError::UnixFdIndex(0, 0)));
+}
+
+#[test]
+fn combine_remap_indices() {
+    let (msg_1, _) = Message::decode(create_msg(2, 0, 1)).unwrap();
+    let (mut msg_2, _) = Message::decode(create_msg(2, 1, 1)).unwrap();
+    // The FDs of the second message are appended to the FDs of the first message.
+    msg_2.remap_unix_fd_indices(4, |i| i + 2);
+    for value in msg_1.get_body() {
+        msg_2.add_value(value.clone());
+    }
+    assert_eq!(msg_2.get_body_fd_indices(), vec![3, 3, 0, 1]);
+    assert_eq!(msg_2.get_unix_fds(), Some(4));
+    assert_eq!(msg_2.validate(), Ok(()));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn forward_detach_fds() {
+    let fds = open_fds(3);
+    let (mut msg, _, _) = Message::decode_with_fds(create_msg(3, 2, 0), &fds).unwrap();
+    assert_eq!(msg.get_body_fds().len(), 2);
+
+    let detached = msg.detach_fds();
+    assert_eq!(msg.get_body_fd_indices(), vec![0, 1]);
+    assert_eq!(msg.get_unix_fds(), Some(2));
+
+    let bytes = msg.encode().unwrap().freeze();
+    let (mut msg, _, offset_fds) = Message::decode_with_fds(bytes.clone(), &detached).unwrap();
+    assert_eq!(offset_fds, 2);
+    let (msg_index, _) = Message::decode(bytes).unwrap();
+    msg.detach_fds();
+    assert_eq!(msg, msg_index);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn mixed_fds_error() {
+    let fds = open_fds(1);
+    let (mut msg, _) = Message::decode(create_msg(2, 0, 1)).unwrap();
+    msg.add_value(Value::UnixFD(fds[0].clone()));
+    assert_eq!(msg.encode(), Err(EncodeError::MixedUnixFds));
+    assert_eq!(msg.validate(), Err(vec![ValidationError::MixedUnixFds]));
+}
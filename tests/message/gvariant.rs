@@ -0,0 +1,183 @@
+#[cfg(target_family = "unix")]
+use super::open_fds;
+use bytes::Bytes;
+use dbus_message_parser::decode::{DecodeError, DecodeOptions, DecodePathElement};
+use dbus_message_parser::encode::EncodeError;
+use dbus_message_parser::message::{Message, GVARIANT_PROTOCOL_VERSION, MAJOR_PROTOCOL_VERSION};
+#[cfg(target_family = "unix")]
+use dbus_message_parser::value::UnixFd;
+use dbus_message_parser::value::{Array, Type, Value};
+#[cfg(target_family = "unix")]
+use std::convert::TryFrom;
+use std::convert::TryInto;
+
+fn create_method_call() -> Message {
+    let mut msg = Message::method_call(
+        "destination.address".try_into().unwrap(),
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "MethodName".try_into().unwrap(),
+    );
+    msg.set_version(GVARIANT_PROTOCOL_VERSION);
+    msg
+}
+
+fn encode_decode(msg: &Message) {
+    let bytes = msg.encode_gvariant().unwrap();
+    assert_eq!(bytes[3], GVARIANT_PROTOCOL_VERSION);
+    assert_eq!(Message::decode_gvariant(bytes.freeze()).as_ref(), Ok(msg));
+}
+
+#[test]
+fn empty_body() {
+    let mut msg = create_method_call();
+    encode_decode(&msg);
+    msg.set_is_le(false);
+    encode_decode(&msg);
+}
+
+#[test]
+fn body() {
+    let mut msg = create_method_call();
+    msg.add_value(Value::String("String Argument".to_string()));
+    msg.add_value(Value::Uint32(0));
+    let array = vec![Value::Int16(1), Value::Int16(2)];
+    msg.add_value(Value::Array(Array::new(array, Type::Int16).unwrap()));
+    msg.add_value(Value::Variant(Box::new(Value::Double(1.0))));
+    encode_decode(&msg);
+    msg.set_is_le(false);
+    encode_decode(&msg);
+
+    let mut msg = msg.method_return().unwrap();
+    msg.set_version(GVARIANT_PROTOCOL_VERSION);
+    msg.set_serial(2);
+    encode_decode(&msg);
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn fds() {
+    let mut fds = open_fds(2);
+    let mut msg = create_method_call();
+    msg.add_value(Value::UnixFD(fds.pop().unwrap()));
+    msg.add_value(Value::UnixFD(fds.pop().unwrap()));
+    let (bytes, fds_encoded) = msg.encode_gvariant_with_fds().unwrap();
+    let fds: Vec<UnixFd> = fds_encoded
+        .into_iter()
+        .map(|fd| UnixFd::try_from(fd).unwrap())
+        .collect();
+
+    let (decoded, offset_fds) = Message::decode_gvariant_with_fds(bytes.freeze(), &fds).unwrap();
+    assert_eq!(offset_fds, 2);
+    assert_eq!(decoded.get_unix_fds(), Some(2));
+    assert_eq!(decoded.get_signature(), msg.get_signature());
+}
+
+#[test]
+fn encode_unsupported_version() {
+    let mut msg = create_method_call();
+    msg.set_version(MAJOR_PROTOCOL_VERSION);
+    assert_eq!(
+        msg.encode_gvariant(),
+        Err(EncodeError::UnsupportedVersion(MAJOR_PROTOCOL_VERSION))
+    );
+}
+
+#[test]
+fn decode_unsupported_version() {
+    let mut msg = create_method_call();
+    msg.set_version(MAJOR_PROTOCOL_VERSION);
+    let bytes = msg.encode().unwrap();
+    assert_eq!(
+        Message::decode_gvariant(bytes.freeze()).map_err(DecodeError::from),
+        Err(DecodeError::UnsupportedVersion(MAJOR_PROTOCOL_VERSION))
+    );
+
+    let bytes = create_method_call().encode_gvariant().unwrap();
+    assert_eq!(
+        Message::decode(bytes.freeze()).map_err(DecodeError::from),
+        Err(DecodeError::UnsupportedVersion(GVARIANT_PROTOCOL_VERSION))
+    );
+}
+
+#[test]
+fn serial_overflow() {
+    let mut bytes = create_method_call().encode_gvariant().unwrap();
+    bytes[12] = 1;
+    assert_eq!(
+        Message::decode_gvariant(bytes.freeze()).map_err(DecodeError::from),
+        Err(DecodeError::SerialOverflow(0x0000_0001_0000_0000))
+    );
+}
+
+#[test]
+fn header_field_number_error() {
+    let mut bytes = create_method_call().encode_gvariant().unwrap();
+    // The number of the first header field is a u64 at the start of the header fields.
+    let number = bytes[16] as u64;
+    bytes[17] = 1;
+    let error = Message::decode_gvariant(bytes.freeze()).unwrap_err();
+    assert_eq!(
+        error.get_error(),
+        &DecodeError::HeaderFieldNumber(0x100 + number)
+    );
+    assert_eq!(error.get_path(), &[DecodePathElement::Header]);
+}
+
+#[test]
+fn not_enough_bytes() {
+    let bytes = create_method_call().encode_gvariant().unwrap().freeze();
+    assert!(Message::decode_gvariant(bytes.slice(..bytes.len() - 1)).is_err());
+    assert!(Message::decode_gvariant(bytes.slice(..10)).is_err());
+    assert!(Message::decode_gvariant(Bytes::new()).is_err());
+}
+
+#[test]
+fn decode_with_options() {
+    let mut msg = create_method_call();
+    let array = vec![Value::Int16(1), Value::Int16(2)];
+    msg.add_value(Value::Array(Array::new(array, Type::Int16).unwrap()));
+    let bytes = msg.encode_gvariant().unwrap().freeze();
+    let options = DecodeOptions {
+        maximum_container_depth: 0,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_gvariant_with_options(bytes.clone(), options).map_err(DecodeError::from),
+        Err(DecodeError::ContainerDepth(1))
+    );
+    assert_eq!(
+        Message::decode_gvariant_with_options(bytes, DecodeOptions::default()),
+        Ok(msg)
+    );
+}
+
+#[test]
+fn index_error() {
+    let mut msg = create_method_call();
+    msg.add_value(Value::UnixFdIndex(1));
+    msg.remap_unix_fd_indices(2, |i| i);
+    let mut bytes = msg.encode_gvariant().unwrap();
+    // The body contains the handle followed by the separator and the type of the body.
+    let body = b"\x01\x00\x00\x00\x00(h)";
+    let start = bytes.windows(body.len()).position(|b| b == body).unwrap();
+    bytes[start] = 2;
+
+    let error = Message::decode_gvariant(bytes.freeze()).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::UnixFdIndex(2, 2));
+    assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(0)]);
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn not_enough_fds() {
+    let mut msg = create_method_call();
+    msg.add_value(Value::UnixFdIndex(0));
+    msg.remap_unix_fd_indices(2, |i| i);
+    let bytes = msg.encode_gvariant().unwrap().freeze();
+    let fds = open_fds(1);
+    assert_eq!(
+        Message::decode_gvariant_with_fds(bytes, &fds).map_err(DecodeError::from),
+        Err(DecodeError::NotEnoughFds(1, 2))
+    );
+}
@@ -0,0 +1,142 @@
+#[cfg(target_family = "unix")]
+use super::{open_fds, raw_fds};
+use bytes::Bytes;
+use dbus_message_parser::decode::{DecodeError, DecodePathElement};
+use dbus_message_parser::message::{LazyMessage, Message};
+use dbus_message_parser::value::{Value, ValueRef};
+use std::convert::TryInto;
+
+fn lazy(msg: &[u8]) {
+    let bytes = Bytes::copy_from_slice(msg);
+    let (lazy_msg, offset_lazy) = LazyMessage::decode(bytes.clone()).unwrap();
+    let (msg, offset) = Message::decode(bytes).unwrap();
+    assert_eq!(offset_lazy, offset);
+    assert_eq!(lazy_msg.get_serial(), msg.get_serial());
+    assert_eq!(lazy_msg.get_type(), msg.get_type());
+    assert_eq!(lazy_msg.get_signature(), &msg.get_signature().unwrap()[..]);
+
+    let body: Vec<Value> = lazy_msg.args().map(Result::unwrap).collect();
+    assert_eq!(&body[..], msg.get_body());
+
+    let body_ref = lazy_msg.decode_body_ref().unwrap();
+    let body: Vec<Value> = body_ref.iter().map(ValueRef::to_value).collect();
+    assert_eq!(&body[..], msg.get_body());
+
+    assert_eq!(lazy_msg.encode().unwrap(), msg.encode().unwrap());
+    assert_eq!(lazy_msg.into_message().unwrap(), msg);
+}
+
+#[test]
+fn msg_1() {
+    let msg = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\x73\x00\
+    \x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\x67\
+    \x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+    \x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\
+    \x00";
+
+    lazy(&msg[..]);
+}
+
+#[test]
+fn msg_3() {
+    let msg = b"\x6c\x04\x01\x01\x74\x00\x00\x00\xb8\x00\x00\x00\x76\x00\x00\x00\x01\x01\x6f\x00\
+    \x10\x00\x00\x00\x2f\x6f\x72\x67\x2f\x67\x6e\x6f\x6d\x65\x2f\x64\x66\x65\x65\x74\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x02\x01\x73\x00\x0f\x00\x00\x00\x6f\x72\x67\x2e\x67\x74\x6b\x2e\x41\x63\
+    \x74\x69\x6f\x6e\x73\x00\x08\x01\x67\x00\x16\x61\x73\x61\x7b\x73\x62\x7d\x61\x7b\x73\x76\x7d\
+    \x61\x7b\x73\x28\x62\x67\x61\x76\x29\x7d\x00\x00\x00\x00\x00\x03\x01\x73\x00\x07\x00\x00\x00\
+    \x43\x68\x61\x6e\x67\x65\x64\x00\x07\x01\x73\x00\x05\x00\x00\x00\x3a\x31\x2e\x38\x39\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x5c\x00\x00\x00\x00\x00\
+    \x00\x00\x04\x00\x00\x00\x68\x65\x6c\x70\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\x00\x00\x61\x62\x6f\x75\x74\x00\x00\x00\
+    \x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\
+    \x00\x71\x75\x69\x74\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x00";
+
+    lazy(&msg[..]);
+}
+
+#[test]
+fn set_serial() {
+    let msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    );
+    let bytes = msg.encode().unwrap().freeze();
+
+    let (mut lazy_msg, _) = LazyMessage::decode(bytes).unwrap();
+    lazy_msg.set_serial(10);
+    let bytes = lazy_msg.encode().unwrap().freeze();
+
+    let (msg, _) = Message::decode(bytes).unwrap();
+    assert_eq!(msg.get_serial(), 10);
+}
+
+#[test]
+fn not_enough_bytes() {
+    let msg = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\x73\x00\
+    \x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\x67\
+    \x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+    \x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38";
+    let bytes = Bytes::copy_from_slice(&msg[..]);
+
+    assert!(LazyMessage::decode(bytes).is_err());
+}
+
+#[test]
+fn args_error_location() {
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    );
+    msg.add_value(Value::Byte(1));
+    msg.add_value(Value::Boolean(true));
+    let mut bytes = msg.encode().unwrap();
+    let len = bytes.len();
+    bytes[len - 4] = 2;
+
+    let (lazy_msg, _) = LazyMessage::decode(bytes.freeze()).unwrap();
+    let mut args = lazy_msg.args();
+    assert_eq!(args.next(), Some(Ok(Value::Byte(1))));
+    let error = args.next().unwrap().unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::InvalidBoolean(2));
+    assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(1)]);
+    assert_eq!(args.next(), None);
+
+    let error = lazy_msg.decode_body().unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::InvalidBoolean(2));
+    assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(1)]);
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn fds() {
+    let fds = open_fds(3);
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    );
+    msg.add_value(Value::UnixFD(fds[0].clone()));
+    msg.add_value(Value::UnixFD(fds[1].clone()));
+    let (bytes, fds_encoded) = msg.encode_with_fds().unwrap();
+    assert_eq!(fds_encoded.len(), 2);
+    let bytes = bytes.freeze();
+
+    let (lazy_msg, offset, offset_fds) = LazyMessage::decode_with_fds(bytes.clone(), &fds).unwrap();
+    assert_eq!(offset, bytes.len());
+    assert_eq!(offset_fds, 2);
+    // The lazy message owns duplicates of the first two FDs.
+    let lazy_fds = raw_fds(lazy_msg.get_fds());
+    assert_eq!(lazy_fds.len(), 2);
+    assert!(lazy_fds.iter().all(|fd| !raw_fds(&fds).contains(fd)));
+    let body = lazy_msg.decode_body().unwrap();
+    assert!(matches!(body[..], [Value::UnixFD(_), Value::UnixFD(_)]));
+
+    let (bytes_lazy, fds_lazy) = lazy_msg.encode_with_fds().unwrap();
+    assert_eq!(bytes_lazy, bytes);
+    assert_eq!(raw_fds(&fds_lazy), lazy_fds);
+
+    assert!(LazyMessage::decode_with_fds(bytes, &fds[..1]).is_err());
+}
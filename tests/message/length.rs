@@ -0,0 +1,71 @@
+use bytes::Bytes;
+use dbus_message_parser::decode::DecodeError;
+use dbus_message_parser::message::{Message, MINIMUM_HEADER_LENGTH};
+
+fn decode_length(msg: &[u8]) {
+    let length = Message::decode_length(msg).unwrap();
+    assert_eq!(length, msg.len());
+
+    let bytes = Bytes::copy_from_slice(msg);
+    let (_, offset) = Message::decode(bytes).unwrap();
+    assert_eq!(length, offset);
+}
+
+#[test]
+fn msg_1() {
+    let msg = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\x73\x00\
+    \x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\x67\
+    \x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+    \x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\
+    \x00";
+
+    decode_length(&msg[..]);
+}
+
+#[test]
+fn msg_2() {
+    let msg = b"\x6c\x01\x00\x01\x00\x00\x00\x00\xbd\x00\x00\x00\x8e\x00\x00\x00\x01\x01\x6f\x00\
+    \x04\x00\x00\x00\x2f\x6f\x72\x67\x00\x00\x00\x00\x02\x01\x73\x00\x23\x00\x00\x00\x6f\x72\x67\
+    \x2e\x66\x72\x65\x65\x64\x65\x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x2e\x49\x6e\x74\x72\x6f\
+    \x73\x70\x65\x63\x74\x61\x62\x6c\x65\x00\x00\x00\x00\x00\x06\x01\x73\x00\x1c\x00\x00\x00\x6f\
+    \x72\x67\x2e\x66\x72\x65\x65\x64\x65\x73\x6b\x74\x6f\x70\x2e\x46\x69\x6c\x65\x4d\x61\x6e\x61\
+    \x67\x65\x72\x31\x00\x00\x00\x00\x03\x01\x73\x00\x0a\x00\x00\x00\x49\x6e\x74\x72\x6f\x73\x70\
+    \x65\x63\x74\x00\x00\x00\x00\x00\x00\x07\x01\x73\x00\x05\x00\x00\x00\x3a\x31\x2e\x38\x39\x00\
+    \x00\x00";
+
+    decode_length(&msg[..]);
+}
+
+#[test]
+fn not_enough_bytes() {
+    let msg = b"\x6c\x01\x00\x01\x00\x00\x00\x00";
+    assert_eq!(
+        Message::decode_length(&msg[..]).map_err(DecodeError::from),
+        Err(DecodeError::NotEnoughBytes(
+            msg.len(),
+            MINIMUM_HEADER_LENGTH
+        ))
+    );
+}
+
+#[test]
+fn endianness_error() {
+    let msg = b"\x00\x01\x00\x01\x00\x00\x00\x00\xbd\x00\x00\x00\x8e\x00\x00\x00";
+    assert_eq!(
+        Message::decode_length(&msg[..]).map_err(DecodeError::from),
+        Err(DecodeError::Endianness(0x00))
+    );
+}
+
+#[test]
+fn too_long() {
+    let msg = b"\x6c\x04\x01\x01\x00\x00\x00\x08\x01\x00\x00\x00\x00\x00\x00\x00";
+    let length = Message::decode_length(&msg[..]).unwrap();
+    assert_eq!(length, 0x08000000 + MINIMUM_HEADER_LENGTH);
+
+    let bytes = Bytes::copy_from_slice(&msg[..]);
+    assert_eq!(
+        Message::decode(bytes).map_err(DecodeError::from),
+        Err(DecodeError::MessageTooLong(length))
+    );
+}
@@ -0,0 +1,121 @@
+use bytes::{Bytes, BytesMut};
+use dbus_message_parser::decode::{DecodeError, DecodePathElement, MessageStreamDecoder};
+use dbus_message_parser::message::Message;
+use dbus_message_parser::value::{Array, Type, TypeError, Value};
+use std::convert::TryInto;
+
+fn create_signal() -> BytesMut {
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    );
+    let dict_entry = |key: &str, value: bool| {
+        Value::DictEntry(Box::new((
+            Value::String(key.to_string()),
+            Value::Variant(Box::new(Value::Boolean(value))),
+        )))
+    };
+    let type_ = Type::DictEntry(Box::new((Type::String, Type::Variant)));
+    let array = Array::new(vec![dict_entry("a", true), dict_entry("b", false)], type_).unwrap();
+    msg.add_value(Value::Uint32(7));
+    msg.add_value(Value::Array(array));
+    msg.encode().unwrap()
+}
+
+/// Returns a message, whose last boolean is invalid, and the path to the boolean.
+fn invalid_boolean() -> (Bytes, Vec<DecodePathElement>) {
+    let mut bytes = create_signal();
+    let len = bytes.len();
+    bytes[len - 4] = 2;
+    let path = vec![
+        DecodePathElement::BodyArgument(1),
+        DecodePathElement::ArrayElement(1),
+        DecodePathElement::DictValue,
+        DecodePathElement::Variant,
+    ];
+    (bytes.freeze(), path)
+}
+
+#[test]
+fn body() {
+    let (bytes, path) = invalid_boolean();
+    let error = Message::decode(bytes.clone()).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::InvalidBoolean(2));
+    assert_eq!(error.get_offset(), bytes.len());
+    assert_eq!(error.get_path(), &path[..]);
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Boolean value only can be 0 or 1: 2 at offset {} \
+            (body arg 1 \u{2192} array element 1 \u{2192} dict value \u{2192} variant)",
+            bytes.len()
+        )
+    );
+}
+
+#[test]
+fn body_ref() {
+    let (bytes, path) = invalid_boolean();
+    let error = Message::decode_ref(&bytes).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::InvalidBoolean(2));
+    assert_eq!(error.get_offset(), bytes.len());
+    assert_eq!(error.get_path(), &path[..]);
+}
+
+#[test]
+fn stream() {
+    let (bytes, path) = invalid_boolean();
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(&bytes);
+    let error = decoder.decode().unwrap_err();
+    assert_eq!(error, DecodeError::InvalidBoolean(2));
+    assert_eq!(error.get_path(), &path[..]);
+}
+
+#[test]
+fn header() {
+    let mut bytes = create_signal();
+    // The version of the protocol.
+    bytes[3] = 2;
+    let error = Message::decode(bytes.freeze()).unwrap_err();
+    assert_eq!(error, DecodeError::UnsupportedVersion(2));
+    assert_eq!(error.get_offset(), 4);
+    assert_eq!(error.get_path(), &[DecodePathElement::Header]);
+}
+
+#[test]
+fn header_field() {
+    let mut bytes = create_signal();
+    // The type of the first header field, which is the object path.
+    assert_eq!(&bytes[17..20], b"\x01o\x00");
+    bytes[18] = b'w';
+    let error = Message::decode(bytes.freeze()).unwrap_err();
+    assert_eq!(
+        error,
+        DecodeError::SignatureError(TypeError::InvalidChar(b'w'))
+    );
+    assert_eq!(error.get_offset(), 20);
+    assert_eq!(
+        error.get_path(),
+        &[
+            DecodePathElement::Header,
+            DecodePathElement::ArrayElement(0),
+            DecodePathElement::StructField(1),
+        ]
+    );
+}
+
+#[test]
+fn not_enough_bytes() {
+    let (bytes, path) = invalid_boolean();
+    let len = bytes.len();
+    let error = Message::decode_ref(&bytes[..len - 2]).unwrap_err();
+    assert_eq!(
+        error.get_error(),
+        &DecodeError::NotEnoughBytes(len - 2, len)
+    );
+    // The error is located at the start of the boolean.
+    assert_eq!(error.get_offset(), len - 4);
+    assert_eq!(error.get_path(), &path[..]);
+}
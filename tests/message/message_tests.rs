@@ -1,4 +1,8 @@
-use dbus_message_parser::message::{Message, MessageType};
+use dbus_message_parser::encode::EncodeError;
+use dbus_message_parser::message::{
+    Message, MessageFlags, MessageHeader, MessageHeaderFields, MessageType, ValidationError,
+    GVARIANT_PROTOCOL_VERSION,
+};
 use dbus_message_parser::value::{ObjectPath, Type, Value};
 use std::convert::TryInto;
 
@@ -343,3 +347,67 @@ fn property_set() {
     assert_eq!(body[1], Value::String("Interfaces".to_string()));
     assert_eq!(body[2], Value::Variant(Box::new(value)));
 }
+
+#[test]
+fn get_unknown_fields() {
+    let msg = create_method_call();
+    assert!(msg.get_unknown_fields().is_empty());
+
+    let fields = MessageHeaderFields {
+        path: Some("/object/path".try_into().unwrap()),
+        member: Some("MethodName".try_into().unwrap()),
+        unknown: vec![(10, Value::String("container".to_string()))],
+        ..Default::default()
+    };
+    let header = MessageHeader::new(
+        true,
+        MessageType::MethodCall,
+        MessageFlags::empty(),
+        1,
+        1,
+        fields,
+    )
+    .unwrap();
+    let msg = Message::new(header, Vec::new());
+
+    let bytes = msg.encode().unwrap().freeze();
+    assert_eq!(msg.encoded_len(), bytes.len());
+    let (msg, _) = Message::decode(bytes).unwrap();
+    assert_eq!(
+        msg.get_unknown_fields(),
+        &[(10, Value::String("container".to_string()))]
+    );
+}
+
+#[test]
+fn unknown_fields_code_error() {
+    for code in [0, 1, 9].iter() {
+        let fields = MessageHeaderFields {
+            path: Some("/object/path".try_into().unwrap()),
+            member: Some("MethodName".try_into().unwrap()),
+            unknown: vec![(*code, Value::Uint32(1))],
+            ..Default::default()
+        };
+        let header = MessageHeader::new(
+            true,
+            MessageType::MethodCall,
+            MessageFlags::empty(),
+            1,
+            1,
+            fields,
+        )
+        .unwrap();
+        let mut msg = Message::new(header, Vec::new());
+
+        assert_eq!(msg.encode(), Err(EncodeError::HeaderFieldCode(*code)));
+        assert_eq!(
+            msg.validate(),
+            Err(vec![ValidationError::HeaderFieldCode(*code)])
+        );
+        msg.set_version(GVARIANT_PROTOCOL_VERSION);
+        assert_eq!(
+            msg.encode_gvariant().map(|_| ()),
+            Err(EncodeError::HeaderFieldCode(*code))
+        );
+    }
+}
@@ -1,4 +1,38 @@
 mod decode_encode_decode;
+mod decode_ref;
 mod encode;
+mod fds;
+mod gvariant;
 mod header;
+mod lazy;
+mod length;
+mod location;
 mod message_tests;
+mod options;
+mod patch;
+mod stream;
+mod swap;
+mod validate;
+mod version;
+
+#[cfg(target_family = "unix")]
+use dbus_message_parser::value::UnixFd;
+#[cfg(target_family = "unix")]
+use std::{
+    fs::File,
+    os::unix::io::{AsRawFd, OwnedFd, RawFd},
+};
+
+/// Open `n` FDs, which are closed, when they are dropped.
+#[cfg(target_family = "unix")]
+fn open_fds(n: usize) -> Vec<UnixFd> {
+    (0..n)
+        .map(|_| UnixFd::from(OwnedFd::from(File::open("/dev/null").unwrap())))
+        .collect()
+}
+
+/// Get the numbers of the FDs.
+#[cfg(target_family = "unix")]
+fn raw_fds<T: AsRawFd>(fds: &[T]) -> Vec<RawFd> {
+    fds.iter().map(AsRawFd::as_raw_fd).collect()
+}
@@ -0,0 +1,205 @@
+use bytes::Bytes;
+use dbus_message_parser::decode::{DecodeError, DecodeOptions, MessageStreamDecoder};
+use dbus_message_parser::message::Message;
+use dbus_message_parser::value::{Array, Type, TypeError, Value};
+use std::convert::TryInto;
+
+fn encode_signal(value: Value) -> Bytes {
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    );
+    msg.add_value(value);
+    msg.encode().unwrap().freeze()
+}
+
+#[test]
+fn default() {
+    let bytes = encode_signal(Value::Uint32(1));
+    assert_eq!(
+        Message::decode_with_options(bytes.clone(), DecodeOptions::default()),
+        Message::decode(bytes)
+    );
+}
+
+#[test]
+fn maximum_message_length() {
+    let bytes = encode_signal(Value::Uint32(1));
+    let options = DecodeOptions {
+        maximum_message_length: bytes.len() - 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_with_options(bytes.clone(), options).map_err(DecodeError::from),
+        Err(DecodeError::MessageTooLong(bytes.len()))
+    );
+
+    let options = DecodeOptions {
+        maximum_message_length: bytes.len(),
+        ..Default::default()
+    };
+    assert!(Message::decode_with_options(bytes, options).is_ok());
+}
+
+#[test]
+fn maximum_array_length() {
+    let array = Array::new(vec![Value::Byte(0); 256], Type::Byte).unwrap();
+    let bytes = encode_signal(Value::Array(array));
+    let options = DecodeOptions {
+        maximum_array_length: 255,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_with_options(bytes.clone(), options).map_err(DecodeError::from),
+        Err(DecodeError::ArrayTooBig(256))
+    );
+
+    let options = DecodeOptions {
+        maximum_array_length: 256,
+        ..Default::default()
+    };
+    assert!(Message::decode_with_options(bytes, options).is_ok());
+}
+
+#[test]
+fn maximum_variant_depth() {
+    let value = Value::Variant(Box::new(Value::Variant(Box::new(Value::Byte(0)))));
+    let bytes = encode_signal(value);
+    let options = DecodeOptions {
+        maximum_variant_depth: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_with_options(bytes, options).map_err(DecodeError::from),
+        Err(DecodeError::VariantDepth(2))
+    );
+}
+
+fn nested_arrays(mut value: Value, depth: usize) -> Value {
+    for _ in 0..depth {
+        let type_ = value.get_type().unwrap();
+        value = Value::Array(Array::new(vec![value], type_).unwrap());
+    }
+    value
+}
+
+#[test]
+fn maximum_container_depth() {
+    // The values of the header fields are already nested in three containers.
+    let value = Value::Struct(
+        vec![Value::Variant(Box::new(nested_arrays(Value::Byte(0), 2)))]
+            .try_into()
+            .unwrap(),
+    );
+    let bytes = encode_signal(value);
+    let options = DecodeOptions {
+        maximum_container_depth: 3,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_with_options(bytes.clone(), options).map_err(DecodeError::from),
+        Err(DecodeError::ContainerDepth(4))
+    );
+
+    let options = DecodeOptions {
+        maximum_container_depth: 4,
+        ..Default::default()
+    };
+    assert!(Message::decode_with_options(bytes, options).is_ok());
+}
+
+#[test]
+fn maximum_container_depth_across_variants() {
+    // Every variant resets the signature depth, but not the total container depth.
+    let mut value = Value::Byte(0);
+    for _ in 0..3 {
+        value = Value::Variant(Box::new(nested_arrays(value, 20)));
+    }
+    let bytes = encode_signal(value.clone());
+    assert!(Message::decode(bytes.clone()).is_ok());
+    assert!(Message::decode_ref(&bytes).is_ok());
+    let mut swapped = bytes.to_vec();
+    assert!(Message::swap_endianness(&mut swapped).is_ok());
+
+    let value = nested_arrays(Value::Variant(Box::new(value)), 1);
+    let bytes = encode_signal(value);
+    assert_eq!(
+        Message::decode(bytes.clone()).map_err(DecodeError::from),
+        Err(DecodeError::ContainerDepth(65))
+    );
+    assert_eq!(
+        Message::decode_ref(&bytes)
+            .map(|_| ())
+            .map_err(DecodeError::from),
+        Err(DecodeError::ContainerDepth(65))
+    );
+    let mut swapped = bytes.to_vec();
+    assert_eq!(
+        Message::swap_endianness(&mut swapped).map_err(DecodeError::from),
+        Err(DecodeError::ContainerDepth(65))
+    );
+}
+
+#[test]
+fn maximum_array_depth() {
+    let array = Array::new(vec![Value::Byte(0)], Type::Byte).unwrap();
+    let array = Array::new(vec![Value::Array(array)], Type::Array(Box::new(Type::Byte))).unwrap();
+    let bytes = encode_signal(Value::Array(array));
+    let options = DecodeOptions {
+        maximum_array_depth: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_with_options(bytes, options).map_err(DecodeError::from),
+        Err(DecodeError::SignatureError(TypeError::ArrayDepth(2)))
+    );
+}
+
+#[test]
+fn maximum_struct_depth() {
+    let struct_ = vec![Value::Byte(0)].try_into().unwrap();
+    let struct_ = vec![Value::Struct(struct_)].try_into().unwrap();
+    let value = Value::Struct(struct_);
+    let bytes = encode_signal(value);
+    let options = DecodeOptions {
+        maximum_struct_depth: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Message::decode_with_options(bytes, options).map_err(DecodeError::from),
+        Err(DecodeError::SignatureError(TypeError::StructDepth(2)))
+    );
+}
+
+#[test]
+fn stream_decoder() {
+    let value = Value::Variant(Box::new(Value::Variant(Box::new(Value::Byte(0)))));
+    let bytes = encode_signal(value);
+    let options = DecodeOptions {
+        maximum_variant_depth: 1,
+        ..Default::default()
+    };
+    let mut decoder = MessageStreamDecoder::with_options(options);
+    decoder.extend_from_slice(&bytes);
+    assert_eq!(
+        decoder.decode().map_err(DecodeError::from),
+        Err(DecodeError::VariantDepth(2))
+    );
+}
+
+#[test]
+fn stream_decoder_too_long() {
+    let bytes = encode_signal(Value::Uint32(1));
+    let options = DecodeOptions {
+        maximum_message_length: bytes.len() - 1,
+        ..Default::default()
+    };
+    let mut decoder = MessageStreamDecoder::with_options(options);
+    decoder.extend_from_slice(&bytes[..16]);
+    assert_eq!(
+        decoder.decode().map_err(DecodeError::from),
+        Err(DecodeError::MessageTooLong(bytes.len()))
+    );
+    assert!(decoder.is_poisoned());
+}
@@ -0,0 +1,126 @@
+use bytes::Bytes;
+use dbus_message_parser::encode::{EncodeError, PatchError};
+use dbus_message_parser::message::Message;
+use dbus_message_parser::value::{Bus, Value};
+use std::convert::TryInto;
+
+const MSG: &[u8] = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\
+    \x73\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\
+    \x01\x67\x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\
+    \x64\x65\x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\
+    \x39\x38\x00";
+
+fn create_signal(is_le: bool) -> Message {
+    let mut signal = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    signal.set_is_le(is_le);
+    signal.add_value(Value::Double(1.0));
+    signal.add_value(Value::String("text".to_string()));
+    signal
+}
+
+fn decode(bytes: &[u8]) -> Message {
+    let (msg, offset) = Message::decode(Bytes::copy_from_slice(bytes)).unwrap();
+    assert_eq!(offset, bytes.len());
+    msg
+}
+
+#[test]
+fn serial() {
+    for is_le in [true, false].iter() {
+        let mut signal = create_signal(*is_le);
+        let mut bytes = signal.encode().unwrap();
+        Message::patch_serial(&mut bytes, 0x01020304).unwrap();
+        signal.set_serial(0x01020304);
+        assert_eq!(bytes, signal.encode().unwrap());
+    }
+}
+
+#[test]
+fn serial_error() {
+    let mut bytes = b"l\x01\x00\x01".to_vec();
+    assert!(Message::patch_serial(&mut bytes, 1).is_err());
+    let mut bytes = b"x\x01\x00\x01\x00\x00\x00\x00\x01\x00\x00\x00".to_vec();
+    assert!(Message::patch_serial(&mut bytes, 1).is_err());
+}
+
+#[test]
+fn serial_zero_error() {
+    let mut bytes = MSG.to_vec();
+    assert_eq!(
+        Message::patch_serial(&mut bytes, 0),
+        Err(PatchError::EncodeError(EncodeError::SerialZero))
+    );
+    assert_eq!(&bytes[..], MSG);
+}
+
+#[test]
+fn reply_serial_insert() {
+    for is_le in [true, false].iter() {
+        let signal = create_signal(*is_le);
+        let bytes = Message::patch_reply_serial(&signal.encode().unwrap(), 10).unwrap();
+        let msg = decode(&bytes);
+        assert_eq!(msg.is_le(), *is_le);
+        assert_eq!(msg.get_reply_serial(), Some(10));
+        assert_eq!(msg.get_path(), signal.get_path());
+        assert_eq!(msg.get_body(), signal.get_body());
+    }
+}
+
+#[test]
+fn reply_serial_replace() {
+    let msg_1 = decode(MSG);
+    let bytes = Message::patch_reply_serial(MSG, 10).unwrap();
+    assert_eq!(bytes.len(), MSG.len());
+    let msg_2 = decode(&bytes);
+    assert_eq!(msg_2.get_reply_serial(), Some(10));
+    assert_eq!(msg_1.get_sender(), msg_2.get_sender());
+    assert_eq!(msg_1.get_destination(), msg_2.get_destination());
+    assert_eq!(msg_1.get_body(), msg_2.get_body());
+}
+
+#[test]
+fn sender_insert() {
+    let sender: Bus = ":1.1000".try_into().unwrap();
+    for is_le in [true, false].iter() {
+        let signal = create_signal(*is_le);
+        let bytes = Message::patch_sender(&signal.encode().unwrap(), &sender).unwrap();
+        let msg = decode(&bytes);
+        assert_eq!(msg.is_le(), *is_le);
+        assert_eq!(msg.get_sender(), Some(&sender));
+        assert_eq!(msg.get_body(), signal.get_body());
+    }
+}
+
+#[test]
+fn sender_replace() {
+    let sender: Bus = ":1.1000".try_into().unwrap();
+    let msg_1 = decode(MSG);
+    let bytes = Message::patch_sender(MSG, &sender).unwrap();
+    let msg_2 = decode(&bytes);
+    assert_eq!(msg_2.get_sender(), Some(&sender));
+    assert_eq!(msg_1.get_reply_serial(), msg_2.get_reply_serial());
+    assert_eq!(msg_1.get_destination(), msg_2.get_destination());
+    assert_eq!(msg_1.get_body(), msg_2.get_body());
+}
+
+#[test]
+fn patch_error() {
+    assert!(Message::patch_reply_serial(&MSG[..MSG.len() - 1], 10).is_err());
+    assert!(Message::patch_sender(&MSG[..20], &":1.1".try_into().unwrap()).is_err());
+}
+
+#[test]
+fn duplicate_header_field_error() {
+    // Replace the sender header field by two more reply serial header fields.
+    let mut bytes = MSG.to_vec();
+    bytes[16..32]
+        .copy_from_slice(b"\x05\x01\x75\x00\x02\x00\x00\x00\x05\x01\x75\x00\x03\x00\x00\x00");
+    assert_eq!(
+        Message::patch_reply_serial(&bytes, 10),
+        Err(PatchError::DuplicateHeaderField(5))
+    );
+}
@@ -0,0 +1,162 @@
+#[cfg(target_family = "unix")]
+use super::{open_fds, raw_fds};
+use bytes::Bytes;
+use dbus_message_parser::decode::{DecodeError, MessageStreamDecoder};
+use dbus_message_parser::message::Message;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::AsRawFd;
+
+static MSG_1: &[u8] = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\
+\x73\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\
+\x67\x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+\x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00";
+
+static MSG_2: &[u8] = b"\x6c\x01\x00\x01\x00\x00\x00\x00\xbd\x00\x00\x00\x8e\x00\x00\x00\x01\x01\
+\x6f\x00\x04\x00\x00\x00\x2f\x6f\x72\x67\x00\x00\x00\x00\x02\x01\x73\x00\x23\x00\x00\x00\x6f\x72\
+\x67\x2e\x66\x72\x65\x65\x64\x65\x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x2e\x49\x6e\x74\x72\x6f\
+\x73\x70\x65\x63\x74\x61\x62\x6c\x65\x00\x00\x00\x00\x00\x06\x01\x73\x00\x1c\x00\x00\x00\x6f\x72\
+\x67\x2e\x66\x72\x65\x65\x64\x65\x73\x6b\x74\x6f\x70\x2e\x46\x69\x6c\x65\x4d\x61\x6e\x61\x67\x65\
+\x72\x31\x00\x00\x00\x00\x03\x01\x73\x00\x0a\x00\x00\x00\x49\x6e\x74\x72\x6f\x73\x70\x65\x63\x74\
+\x00\x00\x00\x00\x00\x00\x07\x01\x73\x00\x05\x00\x00\x00\x3a\x31\x2e\x38\x39\x00\x00\x00";
+
+fn decode(msg: &[u8]) -> Message {
+    let bytes = Bytes::copy_from_slice(msg);
+    let (msg, _) = Message::decode(bytes).unwrap();
+    msg
+}
+
+#[test]
+fn byte_by_byte() {
+    let mut decoder = MessageStreamDecoder::new();
+    for b in &MSG_1[..MSG_1.len() - 1] {
+        decoder.extend_from_slice(&[*b]);
+        assert_eq!(decoder.decode(), Ok(None));
+    }
+    decoder.extend_from_slice(&MSG_1[MSG_1.len() - 1..]);
+    assert_eq!(decoder.decode(), Ok(Some(decode(MSG_1))));
+    assert_eq!(decoder.decode(), Ok(None));
+    assert!(decoder.buffer_mut().is_empty());
+}
+
+#[test]
+fn multiple_messages() {
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(MSG_1);
+    decoder.extend_from_slice(MSG_2);
+    decoder.extend_from_slice(&MSG_1[..10]);
+    assert_eq!(decoder.decode(), Ok(Some(decode(MSG_1))));
+    assert_eq!(decoder.decode(), Ok(Some(decode(MSG_2))));
+    assert_eq!(decoder.decode(), Ok(None));
+    assert_eq!(&decoder.buffer_mut()[..], &MSG_1[..10]);
+}
+
+#[test]
+fn error() {
+    let mut decoder = MessageStreamDecoder::new();
+    let mut msg = MSG_1.to_vec();
+    msg[0] = 0;
+    decoder.extend_from_slice(&msg);
+    assert_eq!(
+        decoder.decode().map_err(DecodeError::from),
+        Err(DecodeError::Endianness(0))
+    );
+    assert!(decoder.is_poisoned());
+
+    // The start of the next message is unknown.
+    decoder.extend_from_slice(MSG_1);
+    assert_eq!(
+        decoder.decode().map_err(DecodeError::from),
+        Err(DecodeError::Endianness(0))
+    );
+}
+
+/// A signal with the body signature `hh`, which references the FDs 0 and 1, and the `UNIX_FDS`
+/// header field 2.
+#[cfg(target_family = "unix")]
+static MSG_FDS: &[u8] = b"\x6c\x01\x00\x01\x08\x00\x00\x00\x00\x00\x00\x00\x68\x00\x00\x00\x01\x01\
+\x6f\x00\x0c\x00\x00\x00\x2f\x6f\x62\x6a\x65\x63\x74\x2f\x70\x61\x74\x68\x00\x00\x00\x00\x02\x01\
+\x73\x00\x11\x00\x00\x00\x69\x6e\x74\x65\x72\x66\x61\x63\x65\x2e\x65\x78\x61\x6d\x70\x6c\x65\
+\x00\x00\x00\x00\x00\x00\x00\x03\x01\x73\x00\x06\x00\x00\x00\x4d\x65\x6d\x62\x65\x72\x00\x00\
+\x06\x01\x73\x00\x04\x00\x00\x00\x3a\x31\x2e\x31\x00\x00\x00\x00\x08\x01\x67\x00\x02\x68\x68\
+\x00\x09\x01\x75\x00\x02\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00";
+
+#[test]
+#[cfg(target_family = "unix")]
+fn fds() {
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(MSG_FDS);
+    let fds = open_fds(3);
+    let last_fd = fds[2].as_raw_fd();
+    decoder.extend_fds(fds);
+    let msg = decoder.decode().unwrap().unwrap();
+    assert_eq!(msg.get_unix_fds(), Some(2));
+    assert_eq!(raw_fds(decoder.get_fds()), vec![last_fd]);
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn not_enough_fds() {
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(MSG_FDS);
+    decoder.extend_from_slice(MSG_1);
+    decoder.extend_fds(open_fds(1));
+    assert_eq!(
+        decoder.decode().map_err(DecodeError::from),
+        Err(DecodeError::NotEnoughFds(1, 2))
+    );
+    assert_eq!(&decoder.buffer_mut()[..], MSG_1);
+    assert!(decoder.get_fds().is_empty());
+    assert!(!decoder.is_poisoned());
+
+    assert_eq!(decoder.decode(), Ok(Some(decode(MSG_1))));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn error_skips_fds() {
+    // The second FD index is not smaller than the UNIX_FDS header field.
+    let mut msg = MSG_FDS.to_vec();
+    let len = msg.len();
+    msg[len - 4] = 2;
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(&msg);
+    decoder.extend_from_slice(MSG_FDS);
+    let fds = open_fds(4);
+    let fds_2 = raw_fds(&fds[2..]);
+    decoder.extend_fds(fds);
+    assert_eq!(
+        decoder.decode().map_err(DecodeError::from),
+        Err(DecodeError::UnixFdIndex(2, 2))
+    );
+    assert_eq!(raw_fds(decoder.get_fds()), fds_2);
+
+    let msg = decoder.decode().unwrap().unwrap();
+    assert_eq!(msg.get_unix_fds(), Some(2));
+    assert!(decoder.get_fds().is_empty());
+}
+
+#[test]
+fn unknown_message_type() {
+    let mut decoder = MessageStreamDecoder::new();
+    let mut msg = MSG_1.to_vec();
+    msg[1] = 0x11;
+    decoder.extend_from_slice(&msg);
+    decoder.extend_from_slice(MSG_2);
+    assert_eq!(decoder.decode(), Ok(Some(decode(MSG_2))));
+    assert!(decoder.buffer_mut().is_empty());
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn unknown_message_type_fds() {
+    let mut msg = MSG_FDS.to_vec();
+    msg[1] = 0x11;
+    let mut decoder = MessageStreamDecoder::new();
+    decoder.extend_from_slice(&msg);
+    decoder.extend_from_slice(MSG_1);
+    let fds = open_fds(3);
+    let last_fd = fds[2].as_raw_fd();
+    decoder.extend_fds(fds);
+    assert_eq!(decoder.decode(), Ok(Some(decode(MSG_1))));
+    assert_eq!(raw_fds(decoder.get_fds()), vec![last_fd]);
+}
@@ -0,0 +1,158 @@
+use bytes::Bytes;
+use dbus_message_parser::decode::{DecodeError, DecodeOptions, DecodePathElement};
+use dbus_message_parser::message::Message;
+use dbus_message_parser::value::{Array, Type, Value};
+use std::convert::TryInto;
+
+fn swap_endianness(msg: &[u8]) {
+    let mut bytes = msg.to_vec();
+    assert_eq!(Message::swap_endianness(&mut bytes), Ok(msg.len()));
+    assert_ne!(&bytes[..], msg);
+
+    // Decode both messages
+    let (msg_1, _) = Message::decode(Bytes::copy_from_slice(msg)).unwrap();
+    let (msg_2, _) = Message::decode(Bytes::copy_from_slice(&bytes)).unwrap();
+    assert_ne!(msg_1.is_le(), msg_2.is_le());
+    assert_eq!(msg_1.get_serial(), msg_2.get_serial());
+    assert_eq!(msg_1.get_type(), msg_2.get_type());
+    assert_eq!(msg_1.get_signature(), msg_2.get_signature());
+    assert_eq!(msg_1.get_body(), msg_2.get_body());
+
+    // Swap back
+    assert_eq!(Message::swap_endianness(&mut bytes), Ok(msg.len()));
+    assert_eq!(&bytes[..], msg);
+}
+
+#[test]
+fn msg_1() {
+    let msg = b"\x6c\x02\x01\x01\x0a\x00\x00\x00\x01\x00\x00\x00\x3d\x00\x00\x00\x06\x01\x73\x00\
+    \x05\x00\x00\x00\x3a\x31\x2e\x39\x38\x00\x00\x00\x05\x01\x75\x00\x01\x00\x00\x00\x08\x01\x67\
+    \x00\x01\x73\x00\x00\x07\x01\x73\x00\x14\x00\x00\x00\x6f\x72\x67\x2e\x66\x72\x65\x65\x64\x65\
+    \x73\x6b\x74\x6f\x70\x2e\x44\x42\x75\x73\x00\x00\x00\x00\x05\x00\x00\x00\x3a\x31\x2e\x39\x38\
+    \x00";
+
+    swap_endianness(&msg[..]);
+}
+
+#[test]
+fn msg_2() {
+    let msg = b"\x6c\x02\x01\x01\xec\x00\x00\x00\x60\x00\x00\x00\x36\x00\x00\x00\x06\x01\x73\x00\
+    \x06\x00\x00\x00\x3a\x31\x2e\x31\x30\x35\x00\x00\x08\x01\x67\x00\x0a\x61\x7b\x73\x28\x62\x67\
+    \x61\x76\x29\x7d\x00\x05\x01\x75\x00\x39\x01\x00\x00\x07\x01\x73\x00\x05\x00\x00\x00\x3a\x31\
+    \x2e\x39\x39\x00\x00\x00\xe4\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x71\x75\x69\x74\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x0c\x00\x00\x00\x6e\x65\x77\x2d\x64\x6f\x63\x75\x6d\x65\x6e\x74\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x0b\x00\x00\x00\x70\x72\
+    \x65\x66\x65\x72\x65\x6e\x63\x65\x73\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x00\x00\x09\x00\x00\x00\x73\x68\x6f\x72\x74\x63\x75\x74\x73\x00\x00\x00\x01\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x68\x65\x6c\x70\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\
+    \x00\x00\x61\x62\x6f\x75\x74\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+    \x00\x00\x00\x00\x00\x00\x00\x0a\x00\x00\x00\x6e\x65\x77\x2d\x77\x69\x6e\x64\x6f\x77\x00\x00\
+    \x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+
+    swap_endianness(&msg[..]);
+}
+
+#[test]
+fn encode_be() {
+    let mut signal = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    signal.add_value(Value::Byte(1));
+    signal.add_value(Value::Int16(-2));
+    signal.add_value(Value::Uint64(3));
+    signal.add_value(Value::Double(4.5));
+    signal.add_value(Value::Boolean(true));
+    signal.add_value(Value::Signature(vec![Type::Int32]));
+    let dict_entry = Value::DictEntry(Box::new((
+        Value::String("key".to_string()),
+        Value::Variant(Box::new(Value::Int32(-6))),
+    )));
+    let array = Array::new(
+        vec![dict_entry],
+        Type::DictEntry(Box::new((Type::String, Type::Variant))),
+    );
+    signal.add_value(Value::Array(array.unwrap()));
+    signal.add_value(Value::Struct(
+        vec![Value::Uint16(7), Value::Variant(Box::new(Value::Int64(-8)))]
+            .try_into()
+            .unwrap(),
+    ));
+    let le = signal.encode().unwrap();
+    signal.set_is_le(false);
+    let be = signal.encode().unwrap();
+
+    let mut bytes = le.to_vec();
+    assert_eq!(Message::swap_endianness(&mut bytes), Ok(le.len()));
+    assert_eq!(&bytes[..], &be[..]);
+
+    swap_endianness(&be[..]);
+}
+
+#[test]
+fn body_length_error() {
+    let signal = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    let mut bytes = signal.encode().unwrap().to_vec();
+    // Declare a body, which is not covered by the signature.
+    bytes[4] = 4;
+    bytes.extend_from_slice(&[0; 4]);
+    assert!(Message::swap_endianness(&mut bytes).is_err());
+}
+
+#[test]
+fn endianness_error() {
+    let mut bytes = b"x\x01\x00\x01\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00".to_vec();
+    assert!(Message::swap_endianness(&mut bytes).is_err());
+}
+
+fn create_signal() -> Message {
+    Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    )
+}
+
+#[test]
+fn padding_error() {
+    let mut signal = create_signal();
+    signal.add_value(Value::Byte(1));
+    signal.add_value(Value::Uint32(2));
+    let mut bytes = signal.encode().unwrap().to_vec();
+    // The padding between the byte and the integer.
+    let len = bytes.len();
+    bytes[len - 7] = 1;
+    let error = Message::swap_endianness(&mut bytes).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::Padding(1));
+    assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(1)]);
+}
+
+#[test]
+fn with_options() {
+    let mut signal = create_signal();
+    let array = Array::new(vec![Value::Int16(1), Value::Int16(2)], Type::Int16).unwrap();
+    signal.add_value(Value::Array(array));
+    let bytes = signal.encode().unwrap().to_vec();
+    let options = DecodeOptions {
+        maximum_container_depth: 0,
+        ..Default::default()
+    };
+    let error = Message::swap_endianness_with_options(&mut bytes.clone(), options).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::ContainerDepth(1));
+    assert_eq!(error.get_path(), &[DecodePathElement::BodyArgument(0)]);
+
+    let options = DecodeOptions {
+        maximum_message_length: bytes.len() - 1,
+        ..Default::default()
+    };
+    let error = Message::swap_endianness_with_options(&mut bytes.clone(), options).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::MessageTooLong(bytes.len()));
+    assert_eq!(error.get_path(), &[DecodePathElement::Header]);
+}
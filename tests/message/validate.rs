@@ -0,0 +1,108 @@
+#[cfg(target_family = "unix")]
+use super::open_fds;
+use dbus_message_parser::message::{Message, ValidationError};
+#[cfg(target_family = "unix")]
+use dbus_message_parser::message::{MessageFlags, MessageHeader, MessageHeaderFields, MessageType};
+use dbus_message_parser::value::{Array, Maybe, Type, TypeError, Value, MAXIMUM_ARRAY_LENGTH};
+use std::convert::TryInto;
+
+fn create_signal() -> Message {
+    Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.example".try_into().unwrap(),
+        "Member".try_into().unwrap(),
+    )
+}
+
+#[test]
+fn valid() {
+    let mut msg = create_signal();
+    msg.add_value(Value::String("Hello".to_string()));
+    let array = Array::new(vec![Value::Int32(1), Value::Int32(2)], Type::Int32).unwrap();
+    msg.add_value(Value::Array(array));
+    msg.add_value(Value::Variant(Box::new(Value::Uint64(3))));
+    assert_eq!(msg.validate(), Ok(()));
+}
+
+#[test]
+fn all_violations() {
+    let mut msg = create_signal();
+    msg.set_version(3);
+    msg.add_value(Value::String("a\0".to_string()));
+    msg.add_value(Value::Maybe(Maybe::nothing(Type::Byte)));
+    msg.add_value(Value::Variant(Box::new(Value::DictEntry(Box::new((
+        Value::Byte(0),
+        Value::Byte(1),
+    ))))));
+    msg.add_value(Value::Signature(vec![Type::DictEntry(Box::new((
+        Type::Byte,
+        Type::Byte,
+    )))]));
+    assert_eq!(
+        msg.validate(),
+        Err(vec![
+            ValidationError::UnsupportedVersion(3),
+            ValidationError::StringNul("a\0".to_string()),
+            ValidationError::MaybeType,
+            ValidationError::Type(TypeError::DictEntryPosition),
+            ValidationError::Type(TypeError::DictEntryPosition),
+        ])
+    );
+}
+
+#[test]
+fn body_signature_too_long() {
+    let mut msg = create_signal();
+    for _ in 0..256 {
+        msg.add_value(Value::Byte(0));
+    }
+    assert_eq!(
+        msg.validate(),
+        Err(vec![ValidationError::BodySignature(
+            TypeError::ExceedMaximum(256)
+        )])
+    );
+}
+
+#[test]
+fn array_too_big() {
+    let string = Value::String("a".repeat(MAXIMUM_ARRAY_LENGTH / 2));
+    let array = Array::new(vec![string.clone(), string], Type::String).unwrap();
+    let mut msg = create_signal();
+    msg.add_value(Value::Array(array));
+    assert_eq!(
+        msg.validate(),
+        Err(vec![ValidationError::ArrayTooBig(
+            MAXIMUM_ARRAY_LENGTH + 13
+        )])
+    );
+    assert!(msg.encode().is_err());
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn unix_fds() {
+    let fields = MessageHeaderFields {
+        path: Some("/object/path".try_into().unwrap()),
+        interface: Some("interface.example".try_into().unwrap()),
+        member: Some("Member".try_into().unwrap()),
+        unix_fds: Some(2),
+        ..Default::default()
+    };
+    let header = MessageHeader::new(
+        true,
+        MessageType::Signal,
+        MessageFlags::empty(),
+        1,
+        1,
+        fields,
+    )
+    .unwrap();
+    let fds = open_fds(2);
+    let msg = Message::new(header.clone(), vec![Value::UnixFD(fds[0].clone())]);
+    assert_eq!(msg.validate(), Err(vec![ValidationError::UnixFds(2, 1)]));
+
+    let body = fds.into_iter().map(Value::UnixFD).collect();
+    let msg = Message::new(header, body);
+    assert_eq!(msg.validate(), Ok(()));
+}
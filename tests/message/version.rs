@@ -0,0 +1,70 @@
+use bytes::BytesMut;
+use dbus_message_parser::decode::DecodeError;
+use dbus_message_parser::encode::{EncodeError, PatchError};
+use dbus_message_parser::message::{
+    Message, MessageFlags, MessageHeader, MessageHeaderFields, MessageType, MAJOR_PROTOCOL_VERSION,
+};
+use std::convert::TryInto;
+
+fn create_message(version: u8) -> Message {
+    let fields = MessageHeaderFields {
+        path: Some("/object/path".try_into().unwrap()),
+        member: Some("MethodName".try_into().unwrap()),
+        ..Default::default()
+    };
+    let header = MessageHeader::new(
+        true,
+        MessageType::MethodCall,
+        MessageFlags::empty(),
+        version,
+        1,
+        fields,
+    )
+    .unwrap();
+    Message::new(header, Vec::new())
+}
+
+#[test]
+fn version() {
+    let msg = create_message(MAJOR_PROTOCOL_VERSION);
+    let bytes = msg.encode().unwrap();
+    assert_eq!(bytes[3], MAJOR_PROTOCOL_VERSION);
+    let (msg, _) = Message::decode(bytes.freeze()).unwrap();
+    assert_eq!(msg.get_version(), MAJOR_PROTOCOL_VERSION);
+    assert_eq!(
+        msg.method_return().unwrap().get_version(),
+        MAJOR_PROTOCOL_VERSION
+    );
+}
+
+#[test]
+fn decode_unsupported_version() {
+    let mut bytes = create_message(MAJOR_PROTOCOL_VERSION).encode().unwrap();
+    bytes[3] = 2;
+    let bytes = bytes.freeze();
+    assert_eq!(
+        Message::decode(bytes.clone()).map_err(DecodeError::from),
+        Err(DecodeError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        Message::swap_endianness(&mut bytes.to_vec()).map_err(DecodeError::from),
+        Err(DecodeError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        Message::patch_reply_serial(&bytes, 1),
+        Err(PatchError::DecodeError(DecodeError::UnsupportedVersion(2)))
+    );
+}
+
+#[test]
+fn encode_unsupported_version() {
+    let msg = create_message(2);
+    assert_eq!(msg.encode(), Err(EncodeError::UnsupportedVersion(2)));
+
+    let mut buf = BytesMut::from(&b"abc"[..]);
+    assert_eq!(
+        msg.encode_into(&mut buf),
+        Err(EncodeError::UnsupportedVersion(2))
+    );
+    assert_eq!(&buf[..], b"abc");
+}
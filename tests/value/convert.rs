@@ -0,0 +1,173 @@
+use dbus_message_parser::message::Message;
+use dbus_message_parser::value::{
+    Array, ConvertError, DBusType, IntoValue, ObjectPath, TryFromValue, Type, TypeError, Value,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::Debug;
+
+fn round_trip<T>(t: T, type_: Type)
+where
+    T: IntoValue + TryFromValue + Clone + Debug + PartialEq,
+{
+    assert_eq!(T::dbus_type(), type_);
+    let value = t.clone().into_value();
+    assert_eq!(value.get_type(), Ok(type_));
+    assert_eq!(T::try_from_value(value), Ok(t));
+}
+
+#[test]
+fn basic_types() {
+    round_trip(1u8, Type::Byte);
+    round_trip(true, Type::Boolean);
+    round_trip(-2i16, Type::Int16);
+    round_trip(3u16, Type::Uint16);
+    round_trip(-4i32, Type::Int32);
+    round_trip(5u32, Type::Uint32);
+    round_trip(-6i64, Type::Int64);
+    round_trip(7u64, Type::Uint64);
+    round_trip(8.5f64, Type::Double);
+    round_trip("text".to_string(), Type::String);
+    let object_path: ObjectPath = "/object/path".try_into().unwrap();
+    round_trip(object_path, Type::ObjectPath);
+}
+
+#[test]
+fn str_into_value() {
+    assert_eq!(<&str>::dbus_type(), Type::String);
+    assert_eq!("text".into_value(), Value::String("text".to_string()));
+}
+
+#[test]
+fn vec() {
+    round_trip(vec![1i32, 2, 3], Type::Array(Box::new(Type::Int32)));
+    round_trip(
+        vec![vec!["a".to_string()], Vec::new()],
+        Type::Array(Box::new(Type::Array(Box::new(Type::String)))),
+    );
+}
+
+#[test]
+fn dicts() {
+    let type_ = Type::Array(Box::new(Type::DictEntry(Box::new((
+        Type::String,
+        Type::Uint32,
+    )))));
+    let mut hash_map = HashMap::new();
+    hash_map.insert("a".to_string(), 1u32);
+    hash_map.insert("b".to_string(), 2u32);
+    round_trip(hash_map, type_.clone());
+
+    let mut btree_map = BTreeMap::new();
+    btree_map.insert("a".to_string(), 1u32);
+    round_trip(btree_map, type_);
+}
+
+#[test]
+fn nested_dicts() {
+    let mut inner = BTreeMap::new();
+    inner.insert(1u8, vec![(true, 2i64)]);
+    let mut dict = HashMap::new();
+    dict.insert(ObjectPath::try_from("/a").unwrap(), inner);
+    let value = vec![dict.clone()].into_value();
+    let type_ = HashMap::<ObjectPath, BTreeMap<u8, Vec<(bool, i64)>>>::dbus_type();
+    let array: Vec<Value> = match value.clone() {
+        Value::Array(array) => array.into(),
+        value => panic!("Value is not an array: {:?}", value),
+    };
+    assert_eq!(
+        Array::new(array, type_).map(Value::Array),
+        Ok(value.clone())
+    );
+    assert_eq!(Vec::try_from_value(value), Ok(vec![dict]));
+}
+
+#[test]
+fn tuples() {
+    round_trip((1u8,), Type::Struct(vec![Type::Byte]));
+    round_trip(
+        ("a".to_string(), 1i32, vec![true]),
+        Type::Struct(vec![
+            Type::String,
+            Type::Int32,
+            Type::Array(Box::new(Type::Boolean)),
+        ]),
+    );
+}
+
+#[test]
+fn option() {
+    round_trip(Some(1u32), Type::Variant);
+    round_trip(None::<u32>, Type::Variant);
+    round_trip(Some(Vec::<u32>::new()), Type::Variant);
+    round_trip(None::<Vec<u32>>, Type::Variant);
+
+    let value = Value::Variant(Box::new(Value::Int32(1)));
+    assert_eq!(
+        Option::<u32>::try_from_value(value),
+        Err(ConvertError::TypeMismatch(Type::Uint32, Type::Int32))
+    );
+}
+
+#[test]
+fn type_mismatch() {
+    assert_eq!(
+        bool::try_from_value(Value::Byte(1)),
+        Err(ConvertError::TypeMismatch(Type::Boolean, Type::Byte))
+    );
+    assert_eq!(
+        Vec::<i32>::try_from_value(vec![1u32].into_value()),
+        Err(ConvertError::TypeMismatch(
+            Type::Array(Box::new(Type::Int32)),
+            Type::Array(Box::new(Type::Uint32))
+        ))
+    );
+    assert_eq!(
+        <(u8, u8)>::try_from_value((1u8,).into_value()),
+        Err(ConvertError::TypeMismatch(
+            Type::Struct(vec![Type::Byte, Type::Byte]),
+            Type::Struct(vec![Type::Byte])
+        ))
+    );
+    assert_eq!(
+        Option::<u8>::try_from_value(Value::Byte(1)),
+        Err(ConvertError::TypeMismatch(Type::Variant, Type::Byte))
+    );
+}
+
+#[test]
+fn type_error() {
+    let key = Value::Array(Array::new(vec![Value::Byte(1)], Type::Byte).unwrap());
+    let dict_entry = Value::DictEntry(Box::new((key, Value::Byte(1))));
+    assert_eq!(
+        u8::try_from_value(dict_entry),
+        Err(ConvertError::TypeError(TypeError::DictEntryKey(
+            Type::Array(Box::new(Type::Byte))
+        )))
+    );
+}
+
+#[test]
+fn message_body() {
+    let mut msg = Message::signal(
+        "/object/path".try_into().unwrap(),
+        "interface.name".try_into().unwrap(),
+        "SignalName".try_into().unwrap(),
+    );
+    let mut dict = HashMap::new();
+    dict.insert("key".to_string(), Some(1u64));
+    msg.add_value(dict.clone().into_value());
+    msg.add_value(("text", 2i16).into_value());
+
+    let bytes = msg.encode().unwrap().freeze();
+    let (msg, _) = Message::decode(bytes).unwrap();
+    let body = msg.get_body();
+    assert_eq!(
+        HashMap::<String, Option<u64>>::try_from_value(body[0].clone()),
+        Ok(dict)
+    );
+    assert_eq!(
+        <(String, i16)>::try_from_value(body[1].clone()),
+        Ok(("text".to_string(), 2))
+    );
+}
@@ -0,0 +1,302 @@
+use dbus_message_parser::decode::{DecodeError, DecodeOptions, DecodePathElement};
+use dbus_message_parser::encode::EncodeError;
+use dbus_message_parser::value::{Array, Maybe, Struct, Type, Value};
+use std::convert::{TryFrom, TryInto};
+
+fn encode_decode(value: Value, bytes: &[u8]) {
+    let type_ = value.get_type().unwrap();
+    assert_eq!(&value.encode_gvariant(true).unwrap()[..], bytes);
+    assert_eq!(Value::decode_gvariant(bytes, &type_, true), Ok(value));
+}
+
+fn encode_decode_round_trip(value: Value) {
+    let type_ = value.get_type().unwrap();
+    for is_le in [true, false].iter() {
+        let bytes = value.encode_gvariant(*is_le).unwrap();
+        assert_eq!(
+            Value::decode_gvariant(&bytes, &type_, *is_le),
+            Ok(value.clone())
+        );
+    }
+}
+
+fn string_array(strings: &[&str]) -> Value {
+    let array = strings
+        .iter()
+        .map(|s| Value::String(s.to_string()))
+        .collect();
+    Value::Array(Array::new(array, Type::String).unwrap())
+}
+
+fn struct_si(s: &str, i: i32) -> Value {
+    Value::Struct(Struct::try_from(vec![Value::String(s.to_string()), Value::Int32(i)]).unwrap())
+}
+
+#[test]
+fn string() {
+    encode_decode(Value::String("hello world".to_string()), b"hello world\x00");
+}
+
+#[test]
+fn boolean_array() {
+    let array = vec![Value::Boolean(true), Value::Boolean(false)];
+    let value = Value::Array(Array::new(array, Type::Boolean).unwrap());
+    encode_decode(value, b"\x01\x00");
+}
+
+#[test]
+fn struct_() {
+    encode_decode(struct_si("foo", -1), b"foo\x00\xff\xff\xff\xff\x04");
+}
+
+#[test]
+fn struct_fixed_size() {
+    let value =
+        Value::Struct(Struct::try_from(vec![Value::Byte(0x70), Value::Int32(0x60)]).unwrap());
+    encode_decode(value, b"\x70\x00\x00\x00\x60\x00\x00\x00");
+}
+
+#[test]
+fn struct_array() {
+    let array = vec![struct_si("hi", -2), struct_si("bye", -1)];
+    let type_ = Type::Struct(vec![Type::String, Type::Int32]);
+    let value = Value::Array(Array::new(array, type_).unwrap());
+    encode_decode(
+        value,
+        b"hi\x00\x00\xfe\xff\xff\xff\x03\x00\x00\x00bye\x00\xff\xff\xff\xff\x04\x09\x15",
+    );
+}
+
+#[test]
+fn string_array_() {
+    encode_decode(
+        string_array(&["i", "can", "has", "strings?"]),
+        b"i\x00can\x00has\x00strings?\x00\x02\x06\x0a\x13",
+    );
+}
+
+#[test]
+fn dict_entry() {
+    let value = Value::DictEntry(Box::new((
+        Value::String("a key".to_string()),
+        Value::Int32(514),
+    )));
+    let bytes = b"a key\x00\x00\x00\x02\x02\x00\x00\x06";
+    assert_eq!(&value.encode_gvariant(true).unwrap()[..], bytes);
+    let type_ = Type::DictEntry(Box::new((Type::String, Type::Int32)));
+    assert_eq!(Value::decode_gvariant(bytes, &type_, true), Ok(value));
+}
+
+#[test]
+fn integer_array() {
+    let array = vec![Value::Int32(4), Value::Int32(258)];
+    let value = Value::Array(Array::new(array, Type::Int32).unwrap());
+    encode_decode(value, b"\x04\x00\x00\x00\x02\x01\x00\x00");
+}
+
+#[test]
+fn variant() {
+    let value = Value::Variant(Box::new(Value::String("hello".to_string())));
+    encode_decode(value, b"hello\x00\x00s");
+}
+
+#[test]
+fn unix_fd_index() {
+    encode_decode(Value::UnixFdIndex(2), b"\x02\x00\x00\x00");
+}
+
+#[test]
+fn big_endian() {
+    assert_eq!(
+        &Value::Int32(1).encode_gvariant(false).unwrap()[..],
+        b"\x00\x00\x00\x01"
+    );
+    encode_decode_round_trip(Value::Double(1.5));
+}
+
+#[test]
+fn dict() {
+    let type_ = Type::DictEntry(Box::new((Type::String, Type::Variant)));
+    let array = vec![
+        Value::DictEntry(Box::new((
+            Value::String("title".to_string()),
+            Value::Variant(Box::new(Value::String("frobit".to_string()))),
+        ))),
+        Value::DictEntry(Box::new((
+            Value::String("percent".to_string()),
+            Value::Variant(Box::new(Value::Int32(50))),
+        ))),
+    ];
+    encode_decode_round_trip(Value::Array(Array::new(array, type_).unwrap()));
+}
+
+#[test]
+fn nested() {
+    let array = vec![
+        string_array(&["a", "b"]),
+        string_array(&[]),
+        string_array(&["c"]),
+    ];
+    let value = Value::Array(Array::new(array, Type::Array(Box::new(Type::String))).unwrap());
+    let value = Value::Struct(
+        Struct::try_from(vec![
+            value,
+            Value::Signature(vec![Type::Int16]),
+            Value::ObjectPath("/object/path".try_into().unwrap()),
+            Value::Uint64(10),
+            Value::Variant(Box::new(struct_si("x", 1))),
+        ])
+        .unwrap(),
+    );
+    encode_decode_round_trip(value);
+}
+
+#[test]
+fn offset_size() {
+    let strings: Vec<String> = (0..100).map(|i| format!("string {}", i)).collect();
+    let strings: Vec<&str> = strings.iter().map(String::as_str).collect();
+    let value = string_array(&strings);
+    let bytes = value.encode_gvariant(true).unwrap();
+    // The framing offsets need two bytes.
+    assert_eq!(bytes.len(), 990 + 100 * 2);
+    encode_decode_round_trip(value);
+}
+
+#[test]
+fn string_not_null() {
+    assert_eq!(
+        Value::decode_gvariant(b"abc", &Type::String, true).map_err(DecodeError::from),
+        Err(DecodeError::StringNotNull(b'c'))
+    );
+}
+
+#[test]
+fn string_nul() {
+    assert_eq!(
+        Value::decode_gvariant(b"a\x00c\x00", &Type::String, true).map_err(DecodeError::from),
+        Err(DecodeError::StringNul(1))
+    );
+    assert_eq!(
+        Value::String("a\0c".to_string()).encode_gvariant(true),
+        Err(EncodeError::StringNul("a\0c".to_string()))
+    );
+}
+
+#[test]
+fn fixed_size_error() {
+    assert_eq!(
+        Value::decode_gvariant(b"\x00\x00", &Type::Int32, true).map_err(DecodeError::from),
+        Err(DecodeError::FixedSize(4, 2))
+    );
+}
+
+#[test]
+fn array_fixed_size_error() {
+    let type_ = Type::Array(Box::new(Type::Int32));
+    assert_eq!(
+        Value::decode_gvariant(b"\x00\x00\x00", &type_, true).map_err(DecodeError::from),
+        Err(DecodeError::ArrayInvalidLength(3, 4))
+    );
+}
+
+#[test]
+fn framing_offset_error() {
+    let type_ = Type::Array(Box::new(Type::String));
+    assert_eq!(
+        Value::decode_gvariant(b"hi\x00\x09", &type_, true).map_err(DecodeError::from),
+        Err(DecodeError::FramingOffset(9))
+    );
+}
+
+#[test]
+fn variant_separator_error() {
+    assert_eq!(
+        Value::decode_gvariant(b"abc", &Type::Variant, true).map_err(DecodeError::from),
+        Err(DecodeError::VariantSeparator)
+    );
+}
+
+#[test]
+fn maybe_string() {
+    let value = Value::Maybe(Maybe::just(Value::String("hello world".to_string())).unwrap());
+    encode_decode(value, b"hello world\x00\x00");
+    encode_decode(Value::Maybe(Maybe::nothing(Type::String)), b"");
+}
+
+#[test]
+fn maybe_fixed_size() {
+    let value = Value::Maybe(Maybe::just(Value::Int32(1)).unwrap());
+    encode_decode(value, b"\x01\x00\x00\x00");
+}
+
+#[test]
+fn maybe_array() {
+    let type_ = Type::Maybe(Box::new(Type::String));
+    let array = vec![
+        Value::Maybe(Maybe::just(Value::String("a".to_string())).unwrap()),
+        Value::Maybe(Maybe::nothing(Type::String)),
+        Value::Maybe(Maybe::just(Value::String("b".to_string())).unwrap()),
+    ];
+    let value = Value::Array(Array::new(array, type_).unwrap());
+    encode_decode(value.clone(), b"a\x00\x00b\x00\x00\x03\x03\x06");
+    encode_decode_round_trip(Value::Maybe(Maybe::just(value).unwrap()));
+}
+
+#[test]
+fn maybe_error() {
+    let type_ = Type::Maybe(Box::new(Type::String));
+    assert_eq!(
+        Value::decode_gvariant(b"a\x00\x01", &type_, true).map_err(DecodeError::from),
+        Err(DecodeError::Padding(1))
+    );
+}
+
+#[test]
+fn container_depth_error() {
+    let type_ = Type::Array(Box::new(Type::Array(Box::new(Type::Byte))));
+    let options = DecodeOptions {
+        maximum_container_depth: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::decode_gvariant_with_options(b"\x01\x01", &type_, true, options)
+            .map_err(DecodeError::from),
+        Err(DecodeError::ContainerDepth(2))
+    );
+}
+
+#[test]
+fn array_too_big_error() {
+    let type_ = Type::Array(Box::new(Type::Byte));
+    let options = DecodeOptions {
+        maximum_array_length: 2,
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::decode_gvariant_with_options(b"\x01\x02\x03", &type_, true, options)
+            .map_err(DecodeError::from),
+        Err(DecodeError::ArrayTooBig(3))
+    );
+}
+
+#[test]
+fn variant_depth_error() {
+    let options = DecodeOptions {
+        maximum_variant_depth: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::decode_gvariant_with_options(b"\x01\x00y\x00v", &Type::Variant, true, options)
+            .map_err(DecodeError::from),
+        Err(DecodeError::VariantDepth(2))
+    );
+}
+
+#[test]
+fn error_location() {
+    let type_ = Type::Array(Box::new(Type::String));
+    let error = Value::decode_gvariant(b"a\x00bc\x02\x04", &type_, true).unwrap_err();
+    assert_eq!(error.get_error(), &DecodeError::StringNotNull(b'c'));
+    assert_eq!(error.get_offset(), 2);
+    assert_eq!(error.get_path(), &[DecodePathElement::ArrayElement(1)]);
+}
@@ -1,5 +1,7 @@
 mod bus;
+mod convert;
 mod error;
+mod gvariant;
 mod interface;
 mod member;
 mod object_path;
@@ -19,9 +19,58 @@ fn struct_depth_error() {
 }
 
 #[test]
-fn dict_depth_error() {
+fn dict_entry_position_error() {
     let signature = Type::from_string_to_signature("{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{");
-    assert_eq!(signature, Err(TypeError::DictDepth(33)))
+    assert_eq!(signature, Err(TypeError::DictEntryPosition));
+
+    let signature = Type::from_string_to_signature("{sv}");
+    assert_eq!(signature, Err(TypeError::DictEntryPosition));
+
+    let signature = Type::from_string_to_signature("({sv})");
+    assert_eq!(signature, Err(TypeError::DictEntryPosition));
+
+    let signature = Type::from_string_to_signature("a{s{sv}}");
+    assert_eq!(signature, Err(TypeError::DictEntryPosition));
+
+    let signature = Type::from_string_to_signature("ma{sv}m{sv}");
+    assert_eq!(signature, Err(TypeError::DictEntryPosition));
+}
+
+#[test]
+fn dict_entry_key_error() {
+    let signature = Type::from_string_to_signature("a{vs}");
+    assert_eq!(signature, Err(TypeError::DictEntryKey(Type::Variant)));
+
+    let signature = Type::from_string_to_signature("a{(s)s}");
+    assert_eq!(
+        signature,
+        Err(TypeError::DictEntryKey(Type::Struct(vec![Type::String])))
+    );
+}
+
+#[test]
+fn dict_entry() {
+    let signature = Type::from_string_to_signature("a{sv}aa{ya{gi}}").unwrap();
+    assert_eq!(
+        Type::from_signature_to_string(&signature).unwrap(),
+        "a{sv}aa{ya{gi}}"
+    );
+}
+
+#[test]
+fn dict_entry_to_string_error() {
+    let type_ = Type::DictEntry(Box::new((Type::String, Type::Variant)));
+    assert_eq!(
+        Type::from_signature_to_string(&[type_]),
+        Err(TypeError::DictEntryPosition)
+    );
+
+    let type_ = Type::DictEntry(Box::new((Type::Variant, Type::String)));
+    let type_ = Type::Array(Box::new(type_));
+    assert_eq!(
+        Type::from_signature_to_string(&[type_]),
+        Err(TypeError::DictEntryKey(Type::Variant))
+    );
 }
 
 #[test]
@@ -37,12 +86,35 @@ fn too_big_error() {
 
 #[test]
 fn closing_curly_bracket_error() {
-    let signature = Type::from_string_to_signature("{isi");
-    assert_eq!(signature, Err(TypeError::ClosingCurlyBracket(3, b'i')))
+    let signature = Type::from_string_to_signature("a{isi");
+    assert_eq!(signature, Err(TypeError::ClosingCurlyBracket(4, b'i')))
 }
 
 #[test]
 fn too_short() {
-    let signature = Type::from_string_to_signature("{is");
-    assert_eq!(signature, Err(TypeError::TooShort(3, 3)))
+    let signature = Type::from_string_to_signature("a{is");
+    assert_eq!(signature, Err(TypeError::TooShort(4, 4)))
+}
+
+#[test]
+fn maybe() {
+    let signature = Type::from_string_to_signature("msm(si)").unwrap();
+    assert_eq!(
+        signature,
+        vec![
+            Type::Maybe(Box::new(Type::String)),
+            Type::Maybe(Box::new(Type::Struct(vec![Type::String, Type::Int32]))),
+        ]
+    );
+    assert_eq!(
+        Type::from_signature_to_string(&signature),
+        Ok("msm(si)".to_string())
+    );
+    assert_eq!(signature[1].to_string(), "m(si)");
+}
+
+#[test]
+fn maybe_too_short() {
+    let signature = Type::from_string_to_signature("m");
+    assert_eq!(signature, Err(TypeError::TooShort(1, 1)))
 }
@@ -1,4 +1,4 @@
-use dbus_message_parser::value::{Array, Struct, StructError, Type, Value};
+use dbus_message_parser::value::{Array, ArrayError, Struct, StructError, Type, TypeError, Value};
 use std::convert::TryFrom;
 
 #[test]
@@ -95,7 +95,6 @@ fn dict_entry_alignment() {
     assert_eq!(type_.get_alignment(), 8);
 }
 
-#[cfg(target_family = "unix")]
 #[test]
 fn unix_fd_alignment() {
     let type_ = Type::UnixFD;
@@ -115,3 +114,56 @@ fn struct_error() {
     let result = Struct::try_from(Vec::new());
     assert_eq!(result, Err(StructError::Empty));
 }
+
+fn dict_entry(key: Value, value: Value) -> Value {
+    Value::DictEntry(Box::new((key, value)))
+}
+
+#[test]
+fn dict_entry_array() {
+    let type_ = Type::DictEntry(Box::new((Type::String, Type::Int32)));
+    let array = vec![dict_entry(
+        Value::String("key".to_string()),
+        Value::Int32(1),
+    )];
+    let array = Array::new(array, type_.clone()).unwrap();
+    assert_eq!(
+        Value::Array(array).get_type(),
+        Ok(Type::Array(Box::new(type_)))
+    );
+}
+
+#[test]
+fn dict_entry_position_error() {
+    let value = dict_entry(Value::String("key".to_string()), Value::Int32(1));
+    assert_eq!(value.get_type(), Err(TypeError::DictEntryPosition));
+
+    let value = Value::Struct(Struct::try_from(vec![value]).unwrap());
+    assert_eq!(value.get_type(), Err(TypeError::DictEntryPosition));
+
+    let type_ = Type::Struct(vec![Type::DictEntry(Box::new((Type::String, Type::Int32)))]);
+    assert_eq!(
+        Array::new(Vec::new(), type_),
+        Err(ArrayError::TypeError(TypeError::DictEntryPosition))
+    );
+}
+
+#[test]
+fn dict_entry_key_error() {
+    let type_ = Type::DictEntry(Box::new((Type::Variant, Type::Int32)));
+    assert_eq!(
+        Array::new(Vec::new(), type_),
+        Err(ArrayError::TypeError(TypeError::DictEntryKey(
+            Type::Variant
+        )))
+    );
+
+    let value = dict_entry(Value::Variant(Box::new(Value::Byte(0))), Value::Int32(1));
+    let type_ = Type::DictEntry(Box::new((Type::String, Type::Int32)));
+    assert_eq!(
+        Array::new(vec![value], type_),
+        Err(ArrayError::TypeError(TypeError::DictEntryKey(
+            Type::Variant
+        )))
+    );
+}